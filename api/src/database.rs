@@ -0,0 +1,210 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DatabasesRequest {
+    query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")] sort: Option<String>,
+    #[serde(default)] include_replicas: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Database {
+    server_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")] server_description: Option<String>,
+    database_name: String,
+    collation_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")] commit: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] branch_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")] project_name: Option<String>,
+    role_name: String,
+    last_update: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] first_seen: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] created: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] age: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")] flags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")] replicas: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DatabasesResponse {
+    #[serde(skip_serializing_if = "Option::is_none")] databases: Option<Vec<Database>>,
+    #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    ok: bool,
+}
+
+impl DatabasesRequest {
+    pub fn new(query: &str) -> DatabasesRequest {
+        DatabasesRequest {
+            query: query.into(),
+            sort: None,
+            include_replicas: false,
+        }
+    }
+
+    pub fn with_sort(mut self, sort: &str) -> DatabasesRequest {
+        self.sort = Some(sort.into());
+        self
+    }
+
+    pub fn with_include_replicas(mut self, include_replicas: bool) -> DatabasesRequest {
+        self.include_replicas = include_replicas;
+        self
+    }
+
+    pub fn query(&self) -> &String {
+        &self.query
+    }
+
+    pub fn sort(&self) -> &Option<String> {
+        &self.sort
+    }
+
+    pub fn include_replicas(&self) -> bool {
+        self.include_replicas
+    }
+}
+
+impl Database {
+    pub fn new(
+        server_name: &str,
+        server_description: &Option<String>,
+        database_name: &str,
+        collation_name: &str,
+        role_name: &str,
+    ) -> Database {
+        Database {
+            server_name: server_name.into(),
+            server_description: server_description.clone(),
+            database_name: database_name.into(),
+            collation_name: collation_name.into(),
+            commit: None,
+            branch_name: None,
+            project_name: None,
+            role_name: role_name.into(),
+            last_update: None,
+            first_seen: None,
+            created: None,
+            age: None,
+            flags: Vec::new(),
+            replicas: Vec::new(),
+        }
+    }
+
+    pub fn with_metadata(
+        mut self,
+        commit: &Option<i64>,
+        branch_name: &Option<String>,
+        project_name: &Option<String>,
+    ) -> Database {
+        self.commit = *commit;
+        self.branch_name = branch_name.clone();
+        self.project_name = project_name.clone();
+        self
+    }
+
+    pub fn with_last_update(mut self, last_update: i64) -> Database {
+        self.last_update = Some(last_update);
+        self
+    }
+
+    pub fn with_age(mut self, first_seen: i64, created: Option<i64>, age: i64) -> Database {
+        self.first_seen = Some(first_seen);
+        self.created = created;
+        self.age = Some(age);
+        self
+    }
+
+    pub fn with_flags(mut self, flags: &[String]) -> Database {
+        self.flags = flags.to_vec();
+        self
+    }
+
+    pub fn with_replicas(mut self, replicas: &[String]) -> Database {
+        self.replicas = replicas.to_vec();
+        self
+    }
+
+    pub fn server_name(&self) -> &String {
+        &self.server_name
+    }
+
+    pub fn server_description(&self) -> &Option<String> {
+        &self.server_description
+    }
+
+    pub fn database_name(&self) -> &String {
+        &self.database_name
+    }
+
+    pub fn collation_name(&self) -> &String {
+        &self.collation_name
+    }
+
+    pub fn commit(&self) -> &Option<i64> {
+        &self.commit
+    }
+
+    pub fn branch_name(&self) -> &Option<String> {
+        &self.branch_name
+    }
+
+    pub fn project_name(&self) -> &Option<String> {
+        &self.project_name
+    }
+
+    pub fn role_name(&self) -> &String {
+        &self.role_name
+    }
+
+    pub fn last_update(&self) -> Option<i64> {
+        self.last_update
+    }
+
+    pub fn first_seen(&self) -> Option<i64> {
+        self.first_seen
+    }
+
+    pub fn created(&self) -> Option<i64> {
+        self.created
+    }
+
+    pub fn age(&self) -> Option<i64> {
+        self.age
+    }
+
+    pub fn flags(&self) -> &Vec<String> {
+        &self.flags
+    }
+
+    pub fn replicas(&self) -> &Vec<String> {
+        &self.replicas
+    }
+}
+
+impl DatabasesResponse {
+    pub fn ok(databases: Vec<Database>) -> DatabasesResponse {
+        DatabasesResponse {
+            databases: Some(databases),
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn err(message: &str) -> DatabasesResponse {
+        DatabasesResponse {
+            databases: None,
+            message: Some(message.into()),
+            ok: false,
+        }
+    }
+
+    pub fn databases(&self) -> &Option<Vec<Database>> {
+        &self.databases
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+}
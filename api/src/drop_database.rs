@@ -0,0 +1,68 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DropDatabaseResponse {
+    #[serde(default)] dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")] backends: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] confirm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")] expires_in: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    ok: bool,
+}
+
+impl DropDatabaseResponse {
+    pub fn planned(backends: i64, confirm: &str, expires_in: u64) -> DropDatabaseResponse {
+        DropDatabaseResponse {
+            dry_run: true,
+            backends: Some(backends),
+            confirm: Some(confirm.into()),
+            expires_in: Some(expires_in),
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn dropped(backends: i64) -> DropDatabaseResponse {
+        DropDatabaseResponse {
+            dry_run: false,
+            backends: Some(backends),
+            confirm: None,
+            expires_in: None,
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn err(message: &str) -> DropDatabaseResponse {
+        DropDatabaseResponse {
+            dry_run: false,
+            backends: None,
+            confirm: None,
+            expires_in: None,
+            message: Some(message.into()),
+            ok: false,
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn backends(&self) -> Option<i64> {
+        self.backends
+    }
+
+    pub fn confirm(&self) -> &Option<String> {
+        &self.confirm
+    }
+
+    pub fn expires_in(&self) -> Option<u64> {
+        self.expires_in
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+}
@@ -0,0 +1,66 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    RefreshFinished {
+        last_update: i64,
+    },
+    ServerHealth {
+        server_name: String,
+        healthy: bool,
+        #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    },
+    DatabaseCreated {
+        server_name: String,
+        database_name: String,
+    },
+    DatabaseDropped {
+        server_name: String,
+        database_name: String,
+    },
+}
+
+impl Event {
+    pub fn refresh_finished(last_update: i64) -> Event {
+        Event::RefreshFinished { last_update }
+    }
+
+    pub fn server_health(server_name: &str, healthy: bool, message: Option<String>) -> Event {
+        Event::ServerHealth {
+            server_name: server_name.into(),
+            healthy,
+            message,
+        }
+    }
+
+    pub fn database_created(server_name: &str, database_name: &str) -> Event {
+        Event::DatabaseCreated {
+            server_name: server_name.into(),
+            database_name: database_name.into(),
+        }
+    }
+
+    pub fn database_dropped(server_name: &str, database_name: &str) -> Event {
+        Event::DatabaseDropped {
+            server_name: server_name.into(),
+            database_name: database_name.into(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Event::RefreshFinished { .. } => "refresh_finished",
+            Event::ServerHealth { .. } => "server_health",
+            Event::DatabaseCreated { .. } => "database_created",
+            Event::DatabaseDropped { .. } => "database_dropped",
+        }
+    }
+
+    pub fn server_name(&self) -> Option<&String> {
+        match *self {
+            Event::RefreshFinished { .. } => None,
+            Event::ServerHealth { ref server_name, .. } => Some(server_name),
+            Event::DatabaseCreated { ref server_name, .. } => Some(server_name),
+            Event::DatabaseDropped { ref server_name, .. } => Some(server_name),
+        }
+    }
+}
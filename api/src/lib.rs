@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate serde_derive;
+
+extern crate serde;
+
+mod database;
+mod drop_database;
+mod event;
+mod refresh;
+mod server;
+mod server_details;
+mod status;
+
+pub use self::database::Database;
+pub use self::database::DatabasesRequest;
+pub use self::database::DatabasesResponse;
+pub use self::drop_database::DropDatabaseResponse;
+pub use self::event::Event;
+pub use self::refresh::RefreshResponse;
+pub use self::server::Server;
+pub use self::server::ServersResponse;
+pub use self::server_details::Role;
+pub use self::server_details::ServerDetails;
+pub use self::server_details::ServerDetailsResponse;
+pub use self::server_details::WalSender;
+pub use self::status::StatusResponse;
+
+pub const OPENAPI_DOCUMENT: &str = include_str!("../openapi.json");
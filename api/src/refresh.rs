@@ -0,0 +1,44 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RefreshResponse {
+    #[serde(skip_serializing_if = "Option::is_none")] retry_after: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    ok: bool,
+}
+
+impl RefreshResponse {
+    pub fn ok() -> RefreshResponse {
+        RefreshResponse {
+            retry_after: None,
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn err(message: &str) -> RefreshResponse {
+        RefreshResponse {
+            retry_after: None,
+            message: Some(message.into()),
+            ok: false,
+        }
+    }
+
+    pub fn too_many_requests(retry_after: u64) -> RefreshResponse {
+        RefreshResponse {
+            retry_after: Some(retry_after),
+            message: Some("Refresh requested too often".into()),
+            ok: false,
+        }
+    }
+
+    pub fn retry_after(&self) -> Option<u64> {
+        self.retry_after
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+}
@@ -0,0 +1,106 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Server {
+    server_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")] server_description: Option<String>,
+    databases: usize,
+    #[serde(default)] management: bool,
+    #[serde(skip_serializing_if = "Option::is_none")] primary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")] replication_lag: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")] replicas: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServersResponse {
+    #[serde(skip_serializing_if = "Option::is_none")] servers: Option<Vec<Server>>,
+    #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    ok: bool,
+}
+
+impl Server {
+    pub fn new(
+        server_name: &str,
+        server_description: &Option<String>,
+        databases: usize,
+        management: bool,
+    ) -> Server {
+        Server {
+            server_name: server_name.into(),
+            server_description: server_description.clone(),
+            databases,
+            management,
+            primary: None,
+            replication_lag: None,
+            replicas: Vec::new(),
+        }
+    }
+
+    pub fn with_replication(
+        mut self,
+        primary: &Option<String>,
+        replication_lag: Option<i64>,
+        replicas: &[String],
+    ) -> Server {
+        self.primary = primary.clone();
+        self.replication_lag = replication_lag;
+        self.replicas = replicas.to_vec();
+        self
+    }
+
+    pub fn server_name(&self) -> &String {
+        &self.server_name
+    }
+
+    pub fn server_description(&self) -> &Option<String> {
+        &self.server_description
+    }
+
+    pub fn databases(&self) -> usize {
+        self.databases
+    }
+
+    pub fn management(&self) -> bool {
+        self.management
+    }
+
+    pub fn primary(&self) -> &Option<String> {
+        &self.primary
+    }
+
+    pub fn replication_lag(&self) -> Option<i64> {
+        self.replication_lag
+    }
+
+    pub fn replicas(&self) -> &Vec<String> {
+        &self.replicas
+    }
+}
+
+impl ServersResponse {
+    pub fn ok(servers: Vec<Server>) -> ServersResponse {
+        ServersResponse {
+            servers: Some(servers),
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn err(message: &str) -> ServersResponse {
+        ServersResponse {
+            servers: None,
+            message: Some(message.into()),
+            ok: false,
+        }
+    }
+
+    pub fn servers(&self) -> &Option<Vec<Server>> {
+        &self.servers
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+}
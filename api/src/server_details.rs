@@ -0,0 +1,244 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Role {
+    role_name: String,
+    superuser: bool,
+    login: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WalSender {
+    application_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")] client_addr: Option<String>,
+    state: String,
+    #[serde(skip_serializing_if = "Option::is_none")] lag_bytes: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerDetails {
+    server_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")] server_description: Option<String>,
+    databases: usize,
+    #[serde(skip_serializing_if = "Option::is_none")] version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")] start_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] in_recovery: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")] cluster_size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] max_connections: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")] roles: Vec<Role>,
+    #[serde(skip_serializing_if = "Option::is_none")] last_update: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] primary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")] replication_lag: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")] replicas: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")] wal_senders: Vec<WalSender>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerDetailsResponse {
+    #[serde(skip_serializing_if = "Option::is_none")] server: Option<ServerDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    ok: bool,
+}
+
+impl Role {
+    pub fn new(role_name: &str, superuser: bool, login: bool) -> Role {
+        Role {
+            role_name: role_name.into(),
+            superuser,
+            login,
+        }
+    }
+
+    pub fn role_name(&self) -> &String {
+        &self.role_name
+    }
+
+    pub fn superuser(&self) -> bool {
+        self.superuser
+    }
+
+    pub fn login(&self) -> bool {
+        self.login
+    }
+}
+
+impl WalSender {
+    pub fn new(
+        application_name: &str,
+        client_addr: &Option<String>,
+        state: &str,
+        lag_bytes: Option<i64>,
+    ) -> WalSender {
+        WalSender {
+            application_name: application_name.into(),
+            client_addr: client_addr.clone(),
+            state: state.into(),
+            lag_bytes,
+        }
+    }
+
+    pub fn application_name(&self) -> &String {
+        &self.application_name
+    }
+
+    pub fn client_addr(&self) -> &Option<String> {
+        &self.client_addr
+    }
+
+    pub fn state(&self) -> &String {
+        &self.state
+    }
+
+    pub fn lag_bytes(&self) -> Option<i64> {
+        self.lag_bytes
+    }
+}
+
+impl ServerDetails {
+    pub fn new(
+        server_name: &str,
+        server_description: &Option<String>,
+        databases: usize,
+    ) -> ServerDetails {
+        ServerDetails {
+            server_name: server_name.into(),
+            server_description: server_description.clone(),
+            databases,
+            version: None,
+            start_time: None,
+            in_recovery: None,
+            cluster_size: None,
+            max_connections: None,
+            roles: Vec::new(),
+            last_update: None,
+            primary: None,
+            replication_lag: None,
+            replicas: Vec::new(),
+            wal_senders: Vec::new(),
+        }
+    }
+
+    pub fn with_inventory(
+        mut self,
+        version: &str,
+        start_time: i64,
+        in_recovery: bool,
+        cluster_size: Option<i64>,
+        max_connections: i64,
+    ) -> ServerDetails {
+        self.version = Some(version.into());
+        self.start_time = Some(start_time);
+        self.in_recovery = Some(in_recovery);
+        self.cluster_size = cluster_size;
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    pub fn with_roles(mut self, roles: Vec<Role>) -> ServerDetails {
+        self.roles = roles;
+        self
+    }
+
+    pub fn with_last_update(mut self, last_update: i64) -> ServerDetails {
+        self.last_update = Some(last_update);
+        self
+    }
+
+    pub fn with_replication(
+        mut self,
+        primary: &Option<String>,
+        replication_lag: Option<i64>,
+        replicas: &[String],
+        wal_senders: Vec<WalSender>,
+    ) -> ServerDetails {
+        self.primary = primary.clone();
+        self.replication_lag = replication_lag;
+        self.replicas = replicas.to_vec();
+        self.wal_senders = wal_senders;
+        self
+    }
+
+    pub fn server_name(&self) -> &String {
+        &self.server_name
+    }
+
+    pub fn server_description(&self) -> &Option<String> {
+        &self.server_description
+    }
+
+    pub fn databases(&self) -> usize {
+        self.databases
+    }
+
+    pub fn version(&self) -> &Option<String> {
+        &self.version
+    }
+
+    pub fn start_time(&self) -> Option<i64> {
+        self.start_time
+    }
+
+    pub fn in_recovery(&self) -> Option<bool> {
+        self.in_recovery
+    }
+
+    pub fn cluster_size(&self) -> Option<i64> {
+        self.cluster_size
+    }
+
+    pub fn max_connections(&self) -> Option<i64> {
+        self.max_connections
+    }
+
+    pub fn roles(&self) -> &Vec<Role> {
+        &self.roles
+    }
+
+    pub fn last_update(&self) -> Option<i64> {
+        self.last_update
+    }
+
+    pub fn primary(&self) -> &Option<String> {
+        &self.primary
+    }
+
+    pub fn replication_lag(&self) -> Option<i64> {
+        self.replication_lag
+    }
+
+    pub fn replicas(&self) -> &Vec<String> {
+        &self.replicas
+    }
+
+    pub fn wal_senders(&self) -> &Vec<WalSender> {
+        &self.wal_senders
+    }
+}
+
+impl ServerDetailsResponse {
+    pub fn ok(server: ServerDetails) -> ServerDetailsResponse {
+        ServerDetailsResponse {
+            server: Some(server),
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn err(message: &str) -> ServerDetailsResponse {
+        ServerDetailsResponse {
+            server: None,
+            message: Some(message.into()),
+            ok: false,
+        }
+    }
+
+    pub fn server(&self) -> &Option<ServerDetails> {
+        &self.server
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+}
@@ -0,0 +1,36 @@
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StatusResponse {
+    #[serde(skip_serializing_if = "Option::is_none")] last_update: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")] message: Option<String>,
+    ok: bool,
+}
+
+impl StatusResponse {
+    pub fn ok(last_update: Option<i64>) -> StatusResponse {
+        StatusResponse {
+            last_update,
+            message: None,
+            ok: true,
+        }
+    }
+
+    pub fn err(message: &str) -> StatusResponse {
+        StatusResponse {
+            last_update: None,
+            message: Some(message.into()),
+            ok: false,
+        }
+    }
+
+    pub fn last_update(&self) -> Option<i64> {
+        self.last_update
+    }
+
+    pub fn message(&self) -> &Option<String> {
+        &self.message
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.ok
+    }
+}
@@ -0,0 +1,320 @@
+extern crate database_monitor_api;
+extern crate serde;
+extern crate serde_json;
+
+use std::collections::HashSet;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value;
+
+use database_monitor_api::Database;
+use database_monitor_api::DatabasesRequest;
+use database_monitor_api::DatabasesResponse;
+use database_monitor_api::DropDatabaseResponse;
+use database_monitor_api::Event;
+use database_monitor_api::RefreshResponse;
+use database_monitor_api::Role;
+use database_monitor_api::Server;
+use database_monitor_api::ServerDetails;
+use database_monitor_api::ServerDetailsResponse;
+use database_monitor_api::ServersResponse;
+use database_monitor_api::StatusResponse;
+use database_monitor_api::WalSender;
+use database_monitor_api::OPENAPI_DOCUMENT;
+
+const SCHEMA_PREFIX: &str = "#/components/schemas/";
+
+struct Schemas {
+    schemas: Map<String, Value>,
+    checked: HashSet<String>,
+}
+
+impl Schemas {
+    fn load() -> Schemas {
+        let document: Value = serde_json::from_str(OPENAPI_DOCUMENT).unwrap();
+        let schemas = document["components"]["schemas"].as_object().unwrap().clone();
+
+        Schemas {
+            schemas,
+            checked: HashSet::new(),
+        }
+    }
+
+    fn schema(&self, name: &str) -> &Value {
+        self.schemas
+            .get(name)
+            .unwrap_or_else(|| panic!("schema {} is missing", name))
+    }
+
+    fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => self.schema(reference.trim_start_matches(SCHEMA_PREFIX)),
+            None => schema,
+        }
+    }
+
+    fn check<T>(&mut self, name: &str, values: &[T])
+    where
+        T: Serialize,
+    {
+        for value in values {
+            let json = serde_json::to_value(value).unwrap();
+
+            self.validate(name, &json, self.schema(name));
+        }
+
+        self.checked.insert(name.into());
+    }
+
+    fn check_minimal<T>(&self, name: &str)
+    where
+        T: DeserializeOwned,
+    {
+        let minimal = self.minimal(self.schema(name));
+
+        if let Err(err) = serde_json::from_value::<T>(minimal.clone()) {
+            panic!("{}: required properties {} are not enough: {}", name, minimal, err);
+        }
+    }
+
+    fn validate(&self, path: &str, value: &Value, schema: &Value) {
+        let schema = self.resolve(schema);
+
+        if value.is_null() && schema["nullable"] == Value::Bool(true) {
+            return;
+        }
+
+        match schema["type"].as_str() {
+            Some("object") => self.validate_object(path, value, schema),
+            Some("array") => {
+                let items = value
+                    .as_array()
+                    .unwrap_or_else(|| panic!("{}: {} is not an array", path, value));
+
+                for (index, item) in items.iter().enumerate() {
+                    self.validate(&format!("{}[{}]", path, index), item, &schema["items"]);
+                }
+            }
+            Some("string") => {
+                assert!(value.is_string(), "{}: {} is not a string", path, value);
+
+                if let Some(values) = schema["enum"].as_array() {
+                    assert!(values.contains(value), "{}: {} is not allowed", path, value);
+                }
+            }
+            Some("integer") => {
+                assert!(
+                    value.is_i64() || value.is_u64(),
+                    "{}: {} is not an integer",
+                    path,
+                    value
+                );
+
+                if let Some(minimum) = schema["minimum"].as_i64() {
+                    let number = value.as_i64().unwrap_or(i64::MAX);
+
+                    assert!(number >= minimum, "{}: {} is below minimum", path, number);
+                }
+            }
+            Some("boolean") => assert!(value.is_boolean(), "{}: {} is not a boolean", path, value),
+            other => panic!("{}: unsupported schema type {:?}", path, other),
+        }
+    }
+
+    fn validate_object(&self, path: &str, value: &Value, schema: &Value) {
+        let object = value
+            .as_object()
+            .unwrap_or_else(|| panic!("{}: {} is not an object", path, value));
+        let properties = schema["properties"].as_object().unwrap();
+
+        for required in schema["required"].as_array().into_iter().flatten() {
+            let required = required.as_str().unwrap();
+
+            assert!(object.contains_key(required), "{}.{} is required", path, required);
+        }
+
+        for (key, property) in object {
+            let property_schema = properties
+                .get(key)
+                .unwrap_or_else(|| panic!("{}.{} is not described", path, key));
+
+            self.validate(&format!("{}.{}", path, key), property, property_schema);
+        }
+    }
+
+    fn minimal(&self, schema: &Value) -> Value {
+        let schema = self.resolve(schema);
+
+        match schema["type"].as_str() {
+            Some("object") => {
+                let mut object = Map::new();
+
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    let required = required.as_str().unwrap();
+                    let property = &schema["properties"][required];
+
+                    object.insert(required.into(), self.minimal(property));
+                }
+
+                Value::Object(object)
+            }
+            Some("array") => Value::Array(Vec::new()),
+            Some("string") => Value::String(String::new()),
+            Some("integer") => Value::from(0),
+            Some("boolean") => Value::Bool(false),
+            _ => Value::Null,
+        }
+    }
+}
+
+fn database() -> Database {
+    Database::new(
+        "localhost",
+        &Some("local host".into()),
+        "project_master",
+        "en_US.UTF-8",
+        "user",
+    )
+    .with_metadata(&Some(1024), &Some("master".into()), &Some("project".into()))
+    .with_last_update(1_500_000_000)
+    .with_age(1_400_000_000, Some(1_300_000_000), 86_400)
+    .with_flags(&["stale".to_string()])
+    .with_replicas(&["replica".to_string()])
+}
+
+fn bare_database() -> Database {
+    Database::new("localhost", &None, "scratch", "C", "user")
+}
+
+fn server_details() -> ServerDetails {
+    let roles = vec![Role::new("postgres", true, true)];
+    let wal_senders = vec![
+        WalSender::new("replica", &Some("10.0.0.2".into()), "streaming", Some(0)),
+        WalSender::new("backup", &None, "catchup", None),
+    ];
+
+    ServerDetails::new("localhost", &Some("local host".into()), 3)
+        .with_inventory(
+            "10.4",
+            1_500_000_000,
+            false,
+            Some(64_000_000),
+            100,
+        )
+        .with_roles(roles)
+        .with_last_update(1_500_000_100)
+        .with_replication(&Some("primary".into()), Some(3), &["replica".to_string()], wal_senders)
+}
+
+#[test]
+fn api_types_match_openapi_document() {
+    let mut schemas = Schemas::load();
+
+    schemas.check(
+        "DatabasesRequest",
+        &[
+            DatabasesRequest::new("project"),
+            DatabasesRequest::new("project").with_sort("-age").with_include_replicas(true),
+        ],
+    );
+    schemas.check("Database", &[database(), bare_database()]);
+    schemas.check(
+        "DatabasesResponse",
+        &[
+            DatabasesResponse::ok(vec![database(), bare_database()]),
+            DatabasesResponse::err("Query string too large"),
+        ],
+    );
+    schemas.check(
+        "Server",
+        &[
+            Server::new("localhost", &Some("local host".into()), 3, true),
+            Server::new("replica", &None, 3, false).with_replication(
+                &Some("localhost".into()),
+                Some(2),
+                &["other".to_string()],
+            ),
+        ],
+    );
+    schemas.check(
+        "ServersResponse",
+        &[
+            ServersResponse::ok(vec![Server::new("localhost", &None, 0, false)]),
+            ServersResponse::err("State is not available"),
+        ],
+    );
+    schemas.check("Role", &[Role::new("user", false, true)]);
+    schemas.check(
+        "WalSender",
+        &[WalSender::new("replica", &None, "streaming", Some(10))],
+    );
+    schemas.check(
+        "ServerDetails",
+        &[server_details(), ServerDetails::new("remote", &None, 0)],
+    );
+    schemas.check(
+        "ServerDetailsResponse",
+        &[
+            ServerDetailsResponse::ok(server_details()),
+            ServerDetailsResponse::err("Unknown server"),
+        ],
+    );
+    schemas.check(
+        "RefreshResponse",
+        &[
+            RefreshResponse::ok(),
+            RefreshResponse::err("Refresh failed"),
+            RefreshResponse::too_many_requests(10),
+        ],
+    );
+    schemas.check(
+        "DropDatabaseResponse",
+        &[
+            DropDatabaseResponse::planned(2, "token", 300),
+            DropDatabaseResponse::dropped(2),
+            DropDatabaseResponse::err("Database is protected"),
+        ],
+    );
+    schemas.check(
+        "StatusResponse",
+        &[StatusResponse::ok(Some(1_500_000_000)), StatusResponse::err("Not ready")],
+    );
+    schemas.check(
+        "Event",
+        &[
+            Event::refresh_finished(1_500_000_000),
+            Event::server_health("localhost", false, Some("connection refused".into())),
+            Event::database_created("localhost", "project_master"),
+            Event::database_dropped("localhost", "project_master"),
+        ],
+    );
+
+    let described: HashSet<String> = schemas
+        .schemas
+        .keys()
+        .filter(|name| *name != "ExportRecord")
+        .cloned()
+        .collect();
+
+    assert_eq!(described, schemas.checked);
+}
+
+#[test]
+fn required_properties_are_enough_to_deserialize() {
+    let schemas = Schemas::load();
+
+    schemas.check_minimal::<DatabasesRequest>("DatabasesRequest");
+    schemas.check_minimal::<Database>("Database");
+    schemas.check_minimal::<DatabasesResponse>("DatabasesResponse");
+    schemas.check_minimal::<Server>("Server");
+    schemas.check_minimal::<ServersResponse>("ServersResponse");
+    schemas.check_minimal::<Role>("Role");
+    schemas.check_minimal::<WalSender>("WalSender");
+    schemas.check_minimal::<ServerDetails>("ServerDetails");
+    schemas.check_minimal::<ServerDetailsResponse>("ServerDetailsResponse");
+    schemas.check_minimal::<RefreshResponse>("RefreshResponse");
+    schemas.check_minimal::<DropDatabaseResponse>("DropDatabaseResponse");
+    schemas.check_minimal::<StatusResponse>("StatusResponse");
+}
@@ -0,0 +1,264 @@
+extern crate database_monitor_api;
+extern crate serde;
+extern crate serde_json;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use database_monitor_api::Database;
+use database_monitor_api::DatabasesRequest;
+use database_monitor_api::DatabasesResponse;
+use database_monitor_api::DropDatabaseResponse;
+use database_monitor_api::Event;
+use database_monitor_api::Role;
+use database_monitor_api::Server;
+use database_monitor_api::ServerDetails;
+use database_monitor_api::ServerDetailsResponse;
+use database_monitor_api::ServersResponse;
+use database_monitor_api::StatusResponse;
+use database_monitor_api::WalSender;
+use database_monitor_api::OPENAPI_DOCUMENT;
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: Serialize + DeserializeOwned,
+{
+    let json = serde_json::to_string(value).unwrap();
+
+    serde_json::from_str(&json).unwrap()
+}
+
+fn database() -> Database {
+    Database::new(
+        "localhost",
+        &Some("local host".into()),
+        "project_master",
+        "en_US.UTF-8",
+        "user",
+    )
+    .with_metadata(&Some(1024), &Some("master".into()), &Some("project".into()))
+    .with_last_update(1_500_000_000)
+    .with_flags(&["stale".to_string()])
+    .with_replicas(&["replica".to_string()])
+}
+
+#[test]
+fn databases_request() {
+    let request = DatabasesRequest::new("project master");
+    let with_replicas = DatabasesRequest::new("project")
+        .with_sort("-age")
+        .with_include_replicas(true);
+
+    assert_eq!(round_trip(&request), request);
+    assert_eq!(round_trip(&with_replicas), with_replicas);
+    assert!(!serde_json::from_str::<DatabasesRequest>(r#"{"query": ""}"#)
+        .unwrap()
+        .include_replicas());
+}
+
+#[test]
+fn databases_response() {
+    let response = DatabasesResponse::ok(vec![database()]);
+
+    assert_eq!(round_trip(&response), response);
+}
+
+#[test]
+fn databases_response_without_metadata() {
+    let database = Database::new("localhost", &None, "scratch", "C", "user");
+    let response = DatabasesResponse::ok(vec![database]);
+    let json: Value = serde_json::to_value(&response).unwrap();
+    let record = &json["databases"][0];
+
+    assert!(record.get("server_description").is_none());
+    assert!(record.get("commit").is_none());
+    assert!(record["last_update"].is_null());
+    assert_eq!(round_trip(&response), response);
+}
+
+#[test]
+fn databases_response_error() {
+    let response = DatabasesResponse::err("Query string too large");
+
+    assert_eq!(round_trip(&response), response);
+    assert_eq!(
+        serde_json::to_value(&response).unwrap(),
+        serde_json::json!({ "message": "Query string too large", "ok": false })
+    );
+}
+
+#[test]
+fn servers_response() {
+    let response = ServersResponse::ok(vec![
+        Server::new("localhost", &Some("local host".into()), 3, true).with_replication(
+            &None,
+            None,
+            &["replica".to_string()],
+        ),
+        Server::new("replica", &None, 3, false).with_replication(
+            &Some("localhost".into()),
+            Some(2),
+            &[],
+        ),
+        Server::new("remote", &None, 0, false),
+    ]);
+
+    assert_eq!(round_trip(&response), response);
+}
+
+fn server_details() -> ServerDetails {
+    let roles = vec![Role::new("postgres", true, true), Role::new("user", false, true)];
+    let wal_senders = vec![
+        WalSender::new("replica", &Some("10.0.0.2".into()), "streaming", Some(0)),
+    ];
+
+    ServerDetails::new("localhost", &Some("local host".into()), 3)
+        .with_inventory(
+            "10.4",
+            1_500_000_000,
+            false,
+            Some(64_000_000),
+            100,
+        )
+        .with_roles(roles)
+        .with_last_update(1_500_000_100)
+        .with_replication(&None, None, &["replica".to_string()], wal_senders)
+}
+
+#[test]
+fn server_details_response() {
+    let response = ServerDetailsResponse::ok(server_details());
+    let pending = ServerDetailsResponse::ok(ServerDetails::new("remote", &None, 0));
+
+    assert_eq!(round_trip(&response), response);
+    assert_eq!(round_trip(&pending), pending);
+    assert_eq!(
+        serde_json::to_value(&pending).unwrap(),
+        serde_json::json!({
+            "server": { "server_name": "remote", "databases": 0 },
+            "ok": true
+        })
+    );
+}
+
+#[test]
+fn drop_database_response() {
+    let planned = DropDatabaseResponse::planned(2, "token", 300);
+    let dropped = DropDatabaseResponse::dropped(2);
+
+    assert_eq!(round_trip(&planned), planned);
+    assert_eq!(round_trip(&dropped), dropped);
+    assert_eq!(
+        serde_json::to_value(&planned).unwrap(),
+        serde_json::json!({
+            "dry_run": true,
+            "backends": 2,
+            "confirm": "token",
+            "expires_in": 300,
+            "ok": true
+        })
+    );
+}
+
+#[test]
+fn status_response() {
+    let response = StatusResponse::ok(Some(1_500_000_000));
+
+    assert_eq!(round_trip(&response), response);
+    assert_eq!(
+        round_trip(&StatusResponse::err("State is not available")),
+        StatusResponse::err("State is not available")
+    );
+}
+
+#[test]
+fn event() {
+    let events = vec![
+        Event::refresh_finished(1_500_000_000),
+        Event::server_health("localhost", false, Some("connection refused".into())),
+        Event::server_health("localhost", true, None),
+        Event::database_created("localhost", "project_master"),
+        Event::database_dropped("localhost", "project_master"),
+    ];
+
+    for event in &events {
+        let json = serde_json::to_value(event).unwrap();
+
+        assert_eq!(round_trip(event), *event);
+        assert_eq!(json["type"], event.name());
+    }
+
+    assert_eq!(events[0].server_name(), None);
+    assert_eq!(events[3].server_name(), Some(&"localhost".to_string()));
+}
+
+#[test]
+fn server_json_is_readable() {
+    let json = r#"{
+        "databases": [{
+            "server_name": "localhost",
+            "database_name": "project_master",
+            "collation_name": "C",
+            "role_name": "user",
+            "last_update": 10
+        }],
+        "ok": true
+    }"#;
+    let response: DatabasesResponse = serde_json::from_str(json).unwrap();
+    let databases = response.databases().as_ref().unwrap();
+
+    assert!(response.is_ok());
+    assert_eq!(databases[0].database_name(), "project_master");
+    assert_eq!(databases[0].commit(), &None);
+    assert_eq!(databases[0].last_update(), Some(10));
+}
+
+#[test]
+fn openapi_document_describes_types() {
+    let document: Value = serde_json::from_str(OPENAPI_DOCUMENT).unwrap();
+    let schemas = &document["components"]["schemas"];
+
+    for name in &[
+        "Database",
+        "DatabasesRequest",
+        "DatabasesResponse",
+        "DropDatabaseResponse",
+        "Event",
+        "Role",
+        "Server",
+        "ServerDetails",
+        "ServerDetailsResponse",
+        "ServersResponse",
+        "StatusResponse",
+        "WalSender",
+    ] {
+        assert!(schemas.get(name).is_some(), "schema {} is missing", name);
+    }
+
+    let properties = schemas["Database"]["properties"].as_object().unwrap();
+    let database = serde_json::to_value(database()).unwrap();
+
+    for key in database.as_object().unwrap().keys() {
+        assert!(properties.contains_key(key), "property {} is missing", key);
+    }
+
+    let properties = schemas["Server"]["properties"].as_object().unwrap();
+    let server = Server::new("replica", &None, 1, false).with_replication(
+        &Some("localhost".into()),
+        Some(2),
+        &["cascade".to_string()],
+    );
+    let server = serde_json::to_value(&server).unwrap();
+
+    for key in server.as_object().unwrap().keys() {
+        assert!(properties.contains_key(key), "property {} is missing", key);
+    }
+
+    let properties = schemas["ServerDetails"]["properties"].as_object().unwrap();
+    let server = serde_json::to_value(server_details()).unwrap();
+
+    for key in server.as_object().unwrap().keys() {
+        assert!(properties.contains_key(key), "property {} is missing", key);
+    }
+}
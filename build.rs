@@ -0,0 +1,96 @@
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io::Result as IoResult;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+
+const ASSET_DIRECTORIES: &[&str] = &["public", "template"];
+const VENDOR_FILES: &[&str] = &[
+    "require.min.js",
+    "knockout-min.js",
+    "moment.min.js",
+    "reqwest.min.js",
+    "semantic-ui/semantic.css",
+    "semantic-ui/themes/default/assets/fonts/icons.eot",
+    "semantic-ui/themes/default/assets/fonts/icons.svg",
+    "semantic-ui/themes/default/assets/fonts/icons.ttf",
+    "semantic-ui/themes/default/assets/fonts/icons.woff",
+    "semantic-ui/themes/default/assets/fonts/icons.woff2",
+];
+const SKIP_VENDOR_VARIABLE: &str = "DBMON_SKIP_VENDOR";
+
+fn check_vendor_files(root: &Path) {
+    let missing: Vec<&str> = VENDOR_FILES
+        .iter()
+        .filter(|file| !root.join("public/vendor").join(file).is_file())
+        .cloned()
+        .collect();
+
+    println!("cargo:rerun-if-env-changed={}", SKIP_VENDOR_VARIABLE);
+
+    if missing.is_empty() {
+        return;
+    }
+
+    let message = format!(
+        "public/vendor is incomplete (missing {}), run scripts/vendor.sh",
+        missing.join(", ")
+    );
+
+    if env::var_os(SKIP_VENDOR_VARIABLE).is_some() {
+        println!("cargo:warning={}, the UI will not work", message);
+    } else {
+        eprintln!("{} or set {}=1 to build without the UI", message, SKIP_VENDOR_VARIABLE);
+        process::exit(1);
+    }
+}
+
+fn collect_files(directory: &Path, files: &mut Vec<PathBuf>) -> IoResult<()> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> IoResult<()> {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let output = PathBuf::from(env::var("OUT_DIR").unwrap()).join("assets.rs");
+    let mut files = Vec::new();
+
+    for directory in ASSET_DIRECTORIES {
+        println!("cargo:rerun-if-changed={}", directory);
+        collect_files(&root.join(directory), &mut files)?;
+    }
+
+    check_vendor_files(&root);
+
+    files.sort();
+
+    let mut writer = File::create(output)?;
+
+    writeln!(writer, "static EMBEDDED: &[(&str, &[u8])] = &[")?;
+
+    for file in files {
+        let name = file
+            .strip_prefix(&root)
+            .unwrap()
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        writeln!(writer, "    ({:?}, include_bytes!({:?})),", name, file)?;
+    }
+
+    writeln!(writer, "];")?;
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+extern crate argparse;
+extern crate csv;
+extern crate database_monitor_client;
+extern crate serde_json;
+extern crate time;
+
+mod options;
+mod output;
+mod record;
+
+use std::io::stdout;
+use std::io::Write;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use database_monitor_client::Client;
+use database_monitor_client::Database;
+use database_monitor_client::DatabasesRequest;
+use database_monitor_client::Server;
+
+use crate::options::Command;
+use crate::options::Options;
+use crate::record::database_sort;
+use crate::record::select_fields;
+use crate::record::sort_records;
+
+fn search(client: &Client, options: &Options, writer: &mut dyn Write) -> Result<(), String> {
+    let query = options.arguments().join(" ");
+    let fields = select_fields::<Database>(options.fields())?;
+    let request = match database_sort(options.sort(), options.reverse())? {
+        Some(sort) => DatabasesRequest::new(&query).with_sort(&sort),
+        None => DatabasesRequest::new(&query),
+    };
+    let databases = client.databases(&request).map_err(|err| format!("{}", err))?;
+
+    output::write_records(options.format(), &databases, &fields, writer)
+        .map_err(|err| format!("{}", err))
+}
+
+fn servers(client: &Client, options: &Options, writer: &mut dyn Write) -> Result<(), String> {
+    let fields = select_fields::<Server>(options.fields())?;
+    let mut servers = client.servers().map_err(|err| format!("{}", err))?;
+
+    sort_records(&mut servers, options.sort(), options.reverse())?;
+
+    output::write_records(options.format(), &servers, &fields, writer)
+        .map_err(|err| format!("{}", err))
+}
+
+fn status(client: &Client, options: &Options, writer: &mut dyn Write) -> Result<(), String> {
+    let last_update = client.status().map_err(|err| format!("{}", err))?;
+
+    output::write_status(options.format(), last_update, writer).map_err(|err| format!("{}", err))
+}
+
+fn events(client: &Client, options: &Options, writer: &mut dyn Write) -> Result<(), String> {
+    let delay = Duration::from_secs(options.interval());
+
+    output::write_event_header(options.format(), writer).map_err(|err| format!("{}", err))?;
+
+    loop {
+        match client.events() {
+            Ok(events) => {
+                for event in events {
+                    match event {
+                        Ok(event) => output::write_event(options.format(), &event, writer)
+                            .map_err(|err| format!("{}", err))?,
+                        Err(err) => {
+                            eprintln!("Failed to read event: {}", err);
+
+                            break;
+                        }
+                    }
+                }
+
+                eprintln!("Event stream closed, reconnecting");
+            }
+            Err(err) => eprintln!("Failed to subscribe to events: {}", err),
+        }
+
+        thread::sleep(delay);
+    }
+}
+
+fn main() {
+    let options = Options::from_args();
+    let client = match Client::new(options.url()) {
+        Ok(client) => match options.token() {
+            Some(token) => client.with_token(token),
+            None => client,
+        },
+        Err(err) => {
+            eprintln!("Invalid monitor URL: {}", err);
+
+            process::exit(2);
+        }
+    };
+    let stdout = stdout();
+    let mut writer = stdout.lock();
+    let result = match options.command() {
+        Command::Search => search(&client, &options, &mut writer),
+        Command::Servers => servers(&client, &options, &mut writer),
+        Command::Status => status(&client, &options, &mut writer),
+        Command::Events => events(&client, &options, &mut writer),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+
+        process::exit(1);
+    }
+}
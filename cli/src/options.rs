@@ -0,0 +1,187 @@
+use std::env;
+use std::str::FromStr;
+
+use argparse::ArgumentParser;
+use argparse::List;
+use argparse::Store;
+use argparse::StoreOption;
+use argparse::StoreTrue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Search,
+    Servers,
+    Status,
+    Events,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    url: String,
+    token: Option<String>,
+    command: Command,
+    arguments: Vec<String>,
+    format: OutputFormat,
+    fields: Option<Vec<String>>,
+    sort: Option<String>,
+    reverse: bool,
+    interval: u64,
+}
+
+const DEFAULT_URL: &str = "http://localhost:8080/";
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Command, String> {
+        match text {
+            "search" => Ok(Command::Search),
+            "servers" => Ok(Command::Servers),
+            "status" => Ok(Command::Status),
+            "events" => Ok(Command::Events),
+            _ => Err(format!("Unknown command: {}", text)),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<OutputFormat, String> {
+        match text {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!("Unknown output format: {}", text)),
+        }
+    }
+}
+
+impl Options {
+    pub fn from_args() -> Options {
+        let mut options = Options::default();
+        let mut fields: Option<String> = None;
+
+        {
+            let mut ap = ArgumentParser::new();
+
+            ap.set_description("PostgreSQL database monitor command-line client.");
+            ap.refer(&mut options.url).add_option(
+                &["-u", "--url"],
+                Store,
+                "Database monitor URL (default: http://localhost:8080/)",
+            );
+            ap.refer(&mut options.token).add_option(
+                &["-t", "--token"],
+                StoreOption,
+                "API token (default: DATABASE_MONITOR_TOKEN environment variable)",
+            );
+            ap.refer(&mut options.format).add_option(
+                &["-f", "--format"],
+                Store,
+                "Output format: table, json or csv (default: table)",
+            );
+            ap.refer(&mut fields).add_option(
+                &["-F", "--fields"],
+                StoreOption,
+                "Comma separated list of fields to show",
+            );
+            ap.refer(&mut options.sort).add_option(
+                &["-s", "--sort"],
+                StoreOption,
+                "Field to sort records by (default: server order)",
+            );
+            ap.refer(&mut options.reverse).add_option(
+                &["-r", "--reverse"],
+                StoreTrue,
+                "Reverse sort order",
+            );
+            ap.refer(&mut options.interval).add_option(
+                &["-i", "--interval"],
+                Store,
+                "Reconnect delay in seconds for events command (default: 10)",
+            );
+            ap.refer(&mut options.command).required().add_argument(
+                "command",
+                Store,
+                "Command: search, servers, status or events",
+            );
+            ap.refer(&mut options.arguments)
+                .add_argument("arguments", List, "Command arguments");
+            ap.parse_args_or_exit();
+        }
+
+        if options.token.is_none() {
+            options.token = env::var("DATABASE_MONITOR_TOKEN").ok();
+        }
+
+        options.fields = fields.map(|fields| {
+            fields
+                .split(',')
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty())
+                .collect()
+        });
+
+        options
+    }
+
+    pub fn url(&self) -> &String {
+        &self.url
+    }
+
+    pub fn token(&self) -> &Option<String> {
+        &self.token
+    }
+
+    pub fn command(&self) -> Command {
+        self.command
+    }
+
+    pub fn arguments(&self) -> &Vec<String> {
+        &self.arguments
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    pub fn fields(&self) -> &Option<Vec<String>> {
+        &self.fields
+    }
+
+    pub fn sort(&self) -> &Option<String> {
+        &self.sort
+    }
+
+    pub fn reverse(&self) -> bool {
+        self.reverse
+    }
+
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            url: DEFAULT_URL.into(),
+            token: None,
+            command: Command::Status,
+            arguments: Vec::new(),
+            format: OutputFormat::Table,
+            fields: None,
+            sort: None,
+            reverse: false,
+            interval: 10,
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use csv::Writer as CsvWriter;
+use serde_json::Map;
+use serde_json::Value;
+
+use database_monitor_client::Event;
+
+use crate::options::OutputFormat;
+use crate::record::Cell;
+use crate::record::Record;
+
+fn format_timestamp(timestamp: i64) -> String {
+    let t = time::at(time::Timespec::new(timestamp, 0));
+
+    match time::strftime("%Y-%m-%d %H:%M:%S", &t) {
+        Ok(text) => text,
+        Err(_) => format!("{}", timestamp),
+    }
+}
+
+const EVENT_FIELDS: &[&str] = &[
+    "event",
+    "server",
+    "database",
+    "healthy",
+    "last_update",
+    "message",
+];
+
+fn event_text(event: &Event) -> String {
+    match *event {
+        Event::RefreshFinished { last_update } => {
+            format!("{}: refresh finished", format_timestamp(last_update))
+        }
+        Event::ServerHealth {
+            ref server_name,
+            healthy: true,
+            ..
+        } => format!("{}: server is up", server_name),
+        Event::ServerHealth {
+            ref server_name,
+            ref message,
+            ..
+        } => match *message {
+            Some(ref message) => format!("{}: server is down: {}", server_name, message),
+            None => format!("{}: server is down", server_name),
+        },
+        Event::DatabaseCreated {
+            ref server_name,
+            ref database_name,
+        } => format!("{}: database {} created", server_name, database_name),
+        Event::DatabaseDropped {
+            ref server_name,
+            ref database_name,
+        } => format!("{}: database {} dropped", server_name, database_name),
+    }
+}
+
+fn event_row(event: &Event) -> Vec<String> {
+    let (server_name, database_name, healthy, last_update, message) = match *event {
+        Event::RefreshFinished { last_update } => ("", "", "", last_update.to_string(), ""),
+        Event::ServerHealth {
+            ref server_name,
+            healthy,
+            ref message,
+        } => (
+            server_name.as_str(),
+            "",
+            if healthy { "true" } else { "false" },
+            String::new(),
+            message.as_deref().unwrap_or_default(),
+        ),
+        Event::DatabaseCreated {
+            ref server_name,
+            ref database_name,
+        }
+        | Event::DatabaseDropped {
+            ref server_name,
+            ref database_name,
+        } => (server_name.as_str(), database_name.as_str(), "", String::new(), ""),
+    };
+
+    vec![
+        event.name().into(),
+        server_name.into(),
+        database_name.into(),
+        healthy.into(),
+        last_update,
+        message.into(),
+    ]
+}
+
+fn cell_text(cell: &Cell, human: bool) -> String {
+    match *cell {
+        Cell::Empty => String::new(),
+        Cell::Number(value) => format!("{}", value),
+        Cell::Timestamp(value) if human => format_timestamp(value),
+        Cell::Timestamp(value) => format!("{}", value),
+        Cell::Text(ref value) => value.clone(),
+    }
+}
+
+fn cell_value(cell: &Cell) -> Value {
+    match *cell {
+        Cell::Empty => Value::Null,
+        Cell::Number(value) => Value::from(value),
+        Cell::Timestamp(value) => Value::from(value),
+        Cell::Text(ref value) => Value::from(value.clone()),
+    }
+}
+
+fn write_table<R>(records: &[R], fields: &[String], writer: &mut dyn Write) -> IoResult<()>
+where
+    R: Record,
+{
+    let rows: Vec<Vec<String>> = records
+        .iter()
+        .map(|record| {
+            fields
+                .iter()
+                .map(|field| cell_text(&record.cell(field), true))
+                .collect()
+        })
+        .collect();
+    let mut widths: Vec<usize> = fields.iter().map(|field| field.chars().count()).collect();
+
+    for row in &rows {
+        for (width, text) in widths.iter_mut().zip(row) {
+            *width = (*width).max(text.chars().count());
+        }
+    }
+
+    let header: Vec<String> = fields.iter().map(|field| field.to_uppercase()).collect();
+
+    write_table_row(&header, &widths, writer)?;
+
+    for row in &rows {
+        write_table_row(row, &widths, writer)?;
+    }
+
+    Ok(())
+}
+
+fn write_table_row(row: &[String], widths: &[usize], writer: &mut dyn Write) -> IoResult<()> {
+    let cells: Vec<String> = row.iter()
+        .zip(widths)
+        .map(|(text, width)| format!("{:width$}", text, width = width))
+        .collect();
+
+    writeln!(writer, "{}", cells.join("  ").trim_end())
+}
+
+fn write_json<R>(records: &[R], fields: &[String], writer: &mut dyn Write) -> IoResult<()>
+where
+    R: Record,
+{
+    let values: Vec<Value> = records
+        .iter()
+        .map(|record| {
+            let mut object = Map::new();
+
+            for field in fields {
+                object.insert(field.clone(), cell_value(&record.cell(field)));
+            }
+
+            Value::Object(object)
+        })
+        .collect();
+
+    serde_json::to_writer_pretty(&mut *writer, &values)?;
+
+    writeln!(writer)
+}
+
+fn write_csv<R>(records: &[R], fields: &[String], writer: &mut dyn Write) -> IoResult<()>
+where
+    R: Record,
+{
+    let mut csv_writer = CsvWriter::from_writer(writer);
+
+    csv_writer.write_record(fields)?;
+
+    for record in records {
+        let row: Vec<String> = fields
+            .iter()
+            .map(|field| cell_text(&record.cell(field), false))
+            .collect();
+
+        csv_writer.write_record(&row)?;
+    }
+
+    csv_writer.flush()
+}
+
+pub fn write_records<R>(
+    format: OutputFormat,
+    records: &[R],
+    fields: &[String],
+    writer: &mut dyn Write,
+) -> IoResult<()>
+where
+    R: Record,
+{
+    match format {
+        OutputFormat::Table => write_table(records, fields, writer),
+        OutputFormat::Json => write_json(records, fields, writer),
+        OutputFormat::Csv => write_csv(records, fields, writer),
+    }
+}
+
+pub fn write_status(format: OutputFormat, last_update: Option<i64>, writer: &mut dyn Write) -> IoResult<()> {
+    match (format, last_update) {
+        (OutputFormat::Table, Some(last_update)) => {
+            writeln!(writer, "last update: {}", format_timestamp(last_update))
+        }
+        (OutputFormat::Table, None) => writeln!(writer, "last update: unknown"),
+        (OutputFormat::Json, last_update) => {
+            let mut object = Map::new();
+            object.insert("last_update".into(), Value::from(last_update));
+
+            serde_json::to_writer_pretty(&mut *writer, &Value::Object(object))?;
+
+            writeln!(writer)
+        }
+        (OutputFormat::Csv, last_update) => {
+            writeln!(writer, "last_update")?;
+            writeln!(
+                writer,
+                "{}",
+                last_update.map(|value| value.to_string()).unwrap_or_default()
+            )
+        }
+    }
+}
+
+pub fn write_event_header(format: OutputFormat, writer: &mut dyn Write) -> IoResult<()> {
+    match format {
+        OutputFormat::Csv => writeln!(writer, "{}", EVENT_FIELDS.join(",")),
+        _ => Ok(()),
+    }
+}
+
+pub fn write_event(format: OutputFormat, event: &Event, writer: &mut dyn Write) -> IoResult<()> {
+    match format {
+        OutputFormat::Table => writeln!(writer, "{}", event_text(event)),
+        OutputFormat::Json => {
+            serde_json::to_writer(&mut *writer, event)?;
+
+            writeln!(writer)
+        }
+        OutputFormat::Csv => {
+            let mut csv_writer = CsvWriter::from_writer(&mut *writer);
+
+            csv_writer.write_record(event_row(event))?;
+            csv_writer.flush()
+        }
+    }?;
+
+    writer.flush()
+}
@@ -0,0 +1,191 @@
+use database_monitor_client::Database;
+use database_monitor_client::Server;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cell {
+    Empty,
+    Number(i64),
+    Timestamp(i64),
+    Text(String),
+}
+
+pub trait Record {
+    fn fields() -> &'static [&'static str];
+
+    fn cell(&self, field: &str) -> Cell;
+}
+
+fn text(value: &str) -> Cell {
+    Cell::Text(value.into())
+}
+
+fn optional_text(value: &Option<String>) -> Cell {
+    match *value {
+        Some(ref value) => Cell::Text(value.clone()),
+        None => Cell::Empty,
+    }
+}
+
+const DATABASE_FIELDS: &[&str] = &[
+    "database",
+    "server",
+    "description",
+    "collation",
+    "role",
+    "commit",
+    "branch",
+    "project",
+    "last_update",
+    "first_seen",
+    "created",
+    "age_days",
+    "flags",
+    "replicas",
+];
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+const DATABASE_SORT_FIELDS: &[(&str, &str)] = &[
+    ("database", "name"),
+    ("server", "server"),
+    ("age_days", "age"),
+    ("last_update", "last_update"),
+];
+
+const SERVER_FIELDS: &[&str] = &[
+    "server",
+    "description",
+    "databases",
+    "primary",
+    "replication_lag",
+    "replicas",
+];
+
+impl Record for Database {
+    fn fields() -> &'static [&'static str] {
+        DATABASE_FIELDS
+    }
+
+    fn cell(&self, field: &str) -> Cell {
+        match field {
+            "database" => text(self.database_name()),
+            "server" => text(self.server_name()),
+            "description" => optional_text(self.server_description()),
+            "collation" => text(self.collation_name()),
+            "role" => text(self.role_name()),
+            "commit" => self.commit().map(Cell::Number).unwrap_or(Cell::Empty),
+            "branch" => optional_text(self.branch_name()),
+            "project" => optional_text(self.project_name()),
+            "last_update" => self.last_update()
+                .map(Cell::Timestamp)
+                .unwrap_or(Cell::Empty),
+            "first_seen" => self.first_seen()
+                .map(Cell::Timestamp)
+                .unwrap_or(Cell::Empty),
+            "created" => self.created().map(Cell::Timestamp).unwrap_or(Cell::Empty),
+            "age_days" => self.age()
+                .map(|age| Cell::Number(age / SECONDS_PER_DAY))
+                .unwrap_or(Cell::Empty),
+            "flags" if self.flags().is_empty() => Cell::Empty,
+            "flags" => text(&self.flags().join(",")),
+            "replicas" if self.replicas().is_empty() => Cell::Empty,
+            "replicas" => text(&self.replicas().join(",")),
+            _ => Cell::Empty,
+        }
+    }
+}
+
+impl Record for Server {
+    fn fields() -> &'static [&'static str] {
+        SERVER_FIELDS
+    }
+
+    fn cell(&self, field: &str) -> Cell {
+        match field {
+            "server" => text(self.server_name()),
+            "description" => optional_text(self.server_description()),
+            "databases" => Cell::Number(self.databases() as i64),
+            "primary" => optional_text(self.primary()),
+            "replication_lag" => self.replication_lag()
+                .map(Cell::Number)
+                .unwrap_or(Cell::Empty),
+            "replicas" if self.replicas().is_empty() => Cell::Empty,
+            "replicas" => text(&self.replicas().join(",")),
+            _ => Cell::Empty,
+        }
+    }
+}
+
+pub fn select_fields<R>(fields: &Option<Vec<String>>) -> Result<Vec<String>, String>
+where
+    R: Record,
+{
+    match *fields {
+        Some(ref fields) => {
+            for field in fields {
+                if !R::fields().contains(&field.as_str()) {
+                    return Err(format!(
+                        "Unknown field {}, expected one of: {}",
+                        field,
+                        R::fields().join(", ")
+                    ));
+                }
+            }
+
+            Ok(fields.clone())
+        }
+        None => Ok(R::fields().iter().map(|field| field.to_string()).collect()),
+    }
+}
+
+pub fn sort_records<R>(records: &mut [R], field: &Option<String>, reverse: bool) -> Result<(), String>
+where
+    R: Record,
+{
+    if let Some(ref field) = *field {
+        if !R::fields().contains(&field.as_str()) {
+            return Err(format!(
+                "Unknown sort field {}, expected one of: {}",
+                field,
+                R::fields().join(", ")
+            ));
+        }
+
+        records.sort_by_key(|a| a.cell(field));
+    }
+
+    if reverse {
+        records.reverse();
+    }
+
+    Ok(())
+}
+
+pub fn database_sort(field: &Option<String>, reverse: bool) -> Result<Option<String>, String> {
+    let sort = match *field {
+        Some(ref field) => DATABASE_SORT_FIELDS
+            .iter()
+            .find(|&&(name, _)| name == field)
+            .map(|&(_, sort)| sort)
+            .ok_or_else(|| {
+                let fields: Vec<&str> = DATABASE_SORT_FIELDS
+                    .iter()
+                    .map(|&(name, _)| name)
+                    .collect();
+
+                format!(
+                    "Search results can not be sorted by {}, expected one of: {}",
+                    field,
+                    fields.join(", ")
+                )
+            })?,
+        None if reverse => "weight",
+        None => return Ok(None),
+    };
+
+    if reverse {
+        Ok(Some(format!("-{}", sort)))
+    } else {
+        Ok(Some(sort.into()))
+    }
+}
@@ -0,0 +1,265 @@
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::process::Command;
+use std::process::Output;
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::thread;
+use std::time::Duration;
+
+const CLI: &str = env!("CARGO_BIN_EXE_database-monitor-cli");
+
+struct FakeMonitor {
+    url: String,
+    requests: Receiver<String>,
+}
+
+impl FakeMonitor {
+    fn start(content_type: &'static str, body: &'static str) -> FakeMonitor {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                let mut content_length = 0;
+
+                reader.read_line(&mut request_line).unwrap();
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line.trim().is_empty() {
+                        break;
+                    }
+
+                    let lower = line.to_lowercase();
+
+                    if let Some(value) = lower.strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let _ = sender.send(format!(
+                    "{} {}",
+                    request_line.trim(),
+                    String::from_utf8_lossy(&request_body)
+                ));
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        FakeMonitor { url, requests }
+    }
+
+    fn request(&self) -> String {
+        self.requests.recv_timeout(Duration::from_secs(10)).unwrap()
+    }
+}
+
+fn cli(arguments: &[&str]) -> Command {
+    let mut command = Command::new(CLI);
+    command
+        .args(arguments)
+        .env("TZ", "UTC")
+        .env_remove("DATABASE_MONITOR_TOKEN");
+    command
+}
+
+fn run(monitor: &FakeMonitor, arguments: &[&str]) -> Output {
+    let mut arguments = arguments.to_vec();
+    arguments.extend(&["--url", &monitor.url]);
+
+    cli(&arguments).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "cli failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+const DATABASES: &str = r#"{
+    "databases": [
+        {
+            "server_name": "alpha",
+            "database_name": "project_new",
+            "collation_name": "C",
+            "role_name": "app",
+            "last_update": 1500000000,
+            "age": 86400,
+            "flags": ["stale", "orphaned"]
+        },
+        {
+            "server_name": "beta",
+            "database_name": "project_old",
+            "collation_name": "C",
+            "role_name": "app",
+            "last_update": 1500000000,
+            "age": 864000
+        }
+    ],
+    "ok": true
+}"#;
+
+#[test]
+fn search_passes_sort_to_server() {
+    let monitor = FakeMonitor::start("application/json", DATABASES);
+    let output = run(
+        &monitor,
+        &["search", "--sort", "age_days", "--reverse", "-F", "database,age_days", "project"],
+    );
+    let request = monitor.request();
+
+    assert!(request.starts_with("GET /api/v1/databases?"), "{}", request);
+    assert!(request.contains("q=project"), "{}", request);
+    assert!(request.contains("sort=-age"), "{}", request);
+    assert_eq!(
+        stdout(&output),
+        "DATABASE     AGE_DAYS\nproject_new  1\nproject_old  10\n"
+    );
+}
+
+#[test]
+fn search_rejects_fields_server_can_not_sort_by() {
+    let monitor = FakeMonitor::start("application/json", DATABASES);
+    let output = run(&monitor, &["search", "--sort", "role", "project"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("can not be sorted by role"), "{}", stderr);
+    assert!(monitor.requests.try_recv().is_err());
+}
+
+#[test]
+fn search_writes_csv_and_json() {
+    let monitor = FakeMonitor::start("application/json", DATABASES);
+    let csv = run(&monitor, &["search", "-f", "csv", "-F", "server,database,flags", "project"]);
+    let json = run(&monitor, &["search", "-f", "json", "-F", "database,last_update", "project"]);
+
+    assert_eq!(
+        stdout(&csv),
+        "server,database,flags\nalpha,project_new,\"stale,orphaned\"\nbeta,project_old,\n"
+    );
+
+    let values: serde_json::Value = serde_json::from_str(&stdout(&json)).unwrap();
+
+    assert_eq!(
+        values,
+        serde_json::json!([
+            { "database": "project_new", "last_update": 1500000000 },
+            { "database": "project_old", "last_update": 1500000000 }
+        ])
+    );
+}
+
+#[test]
+fn unknown_options_are_rejected() {
+    let format = cli(&["--format", "xml", "status"]).output().unwrap();
+    let command = cli(&["restart"]).output().unwrap();
+    let monitor = FakeMonitor::start("application/json", DATABASES);
+    let field = run(&monitor, &["search", "-F", "database,size", "project"]);
+
+    assert_eq!(format.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&format.stderr).contains("Bad value xml"));
+    assert_eq!(command.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&command.stderr).contains("Bad value restart"));
+    assert_eq!(field.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&field.stderr).contains("Unknown field size"));
+}
+
+#[test]
+fn status_is_formatted() {
+    let body = r#"{"last_update": 1500000000, "ok": true}"#;
+    let monitor = FakeMonitor::start("application/json", body);
+    let table = run(&monitor, &["status"]);
+    let csv = run(&monitor, &["status", "-f", "csv"]);
+
+    assert_eq!(stdout(&table), "last update: 2017-07-14 02:40:00\n");
+    assert_eq!(stdout(&csv), "last_update\n1500000000\n");
+}
+
+const EVENTS: &str = "\
+: keep-alive\n\n\
+event: refresh_finished\ndata: {\"type\":\"refresh_finished\",\"last_update\":1500000000}\n\n\
+event: server_health\n\
+data: {\"type\":\"server_health\",\"server_name\":\"alpha\",\
+\"healthy\":false,\"message\":\"refused\"}\n\n\
+event: database_created\n\
+data: {\"type\":\"database_created\",\"server_name\":\"alpha\",\"database_name\":\"app\"}\n\n";
+
+fn read_events(format: &str, count: usize) -> (Vec<String>, String) {
+    let monitor = FakeMonitor::start("text/event-stream", EVENTS);
+    let mut child = cli(&["events", "-f", format, "--interval", "60", "--url", &monitor.url])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let reader = BufReader::new(child.stdout.take().unwrap());
+    let lines: Vec<String> = reader.lines().take(count).map(|line| line.unwrap()).collect();
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    (lines, monitor.request())
+}
+
+#[test]
+fn events_are_read_from_event_stream() {
+    let (lines, request) = read_events("table", 3);
+
+    assert!(request.starts_with("GET /api/v1/events "), "{}", request);
+    assert_eq!(
+        lines,
+        vec![
+            "2017-07-14 02:40:00: refresh finished",
+            "alpha: server is down: refused",
+            "alpha: database app created",
+        ]
+    );
+
+    let (lines, _) = read_events("csv", 4);
+
+    assert_eq!(
+        lines,
+        vec![
+            "event,server,database,healthy,last_update,message",
+            "refresh_finished,,,,1500000000,",
+            "server_health,alpha,,false,,refused",
+            "database_created,alpha,app,,,",
+        ]
+    );
+
+    let (lines, _) = read_events("json", 1);
+    let event: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+
+    assert_eq!(
+        event,
+        serde_json::json!({ "type": "refresh_finished", "last_update": 1500000000 })
+    );
+}
@@ -0,0 +1,211 @@
+use reqwest::blocking::Client as HttpClient;
+use reqwest::blocking::RequestBuilder;
+use reqwest::blocking::Response;
+use reqwest::header::ACCEPT;
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+
+use database_monitor_api::Database;
+use database_monitor_api::DatabasesRequest;
+use database_monitor_api::DatabasesResponse;
+use database_monitor_api::DropDatabaseResponse;
+use database_monitor_api::Server;
+use database_monitor_api::ServerDetails;
+use database_monitor_api::ServerDetailsResponse;
+use database_monitor_api::ServersResponse;
+use database_monitor_api::StatusResponse;
+
+use super::ClientError;
+use super::ClientResult;
+use super::Events;
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: Url,
+    http: HttpClient,
+    token: Option<String>,
+}
+
+impl Client {
+    pub fn new(base_url: &str) -> ClientResult<Client> {
+        let mut base_url = Url::parse(base_url)?;
+
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+
+            base_url.set_path(&path);
+        }
+
+        Ok(Client {
+            base_url,
+            http: HttpClient::new(),
+            token: None,
+        })
+    }
+
+    pub fn with_token(mut self, token: &str) -> Client {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn status(&self) -> ClientResult<Option<i64>> {
+        let url = self.url("api/v1/status")?;
+        let response: StatusResponse = self.get(url)?;
+
+        Ok(response.last_update())
+    }
+
+    pub fn databases(&self, request: &DatabasesRequest) -> ClientResult<Vec<Database>> {
+        let mut url = self.url("api/v1/databases")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("q", request.query());
+
+            if let Some(ref sort) = *request.sort() {
+                pairs.append_pair("sort", sort);
+            }
+
+            if request.include_replicas() {
+                pairs.append_pair("include_replicas", "true");
+            }
+        }
+
+        let response: DatabasesResponse = self.get(url)?;
+
+        Ok(response.databases().clone().unwrap_or_default())
+    }
+
+    pub fn servers(&self) -> ClientResult<Vec<Server>> {
+        let url = self.url("api/v1/servers")?;
+        let response: ServersResponse = self.get(url)?;
+
+        Ok(response.servers().clone().unwrap_or_default())
+    }
+
+    pub fn server(&self, server_name: &str) -> ClientResult<ServerDetails> {
+        let url = self.servers_url(&[server_name])?;
+        let response: ServerDetailsResponse = self.get(url)?;
+
+        response.server().clone().ok_or_else(|| ClientError::DecodeError {
+            message: "Server is missing in response".into(),
+        })
+    }
+
+    pub fn server_databases(&self, server_name: &str) -> ClientResult<Vec<Database>> {
+        let url = self.servers_url(&[server_name, "databases"])?;
+        let response: DatabasesResponse = self.get(url)?;
+
+        Ok(response.databases().clone().unwrap_or_default())
+    }
+
+    pub fn plan_drop_database(
+        &self,
+        server_name: &str,
+        database_name: &str,
+    ) -> ClientResult<DropDatabaseResponse> {
+        let mut url = self.servers_url(&[server_name, "databases", database_name])?;
+        url.query_pairs_mut().append_pair("dry_run", "true");
+
+        self.send(self.http.delete(url))
+    }
+
+    pub fn drop_database(
+        &self,
+        server_name: &str,
+        database_name: &str,
+        confirm: &str,
+    ) -> ClientResult<DropDatabaseResponse> {
+        let mut url = self.servers_url(&[server_name, "databases", database_name])?;
+        url.query_pairs_mut().append_pair("confirm", confirm);
+
+        self.send(self.http.delete(url))
+    }
+
+    pub fn export(&self, request: &DatabasesRequest, format: &str) -> ClientResult<Response> {
+        let mut url = self.url("api/v1/databases/export")?;
+        url.query_pairs_mut().append_pair("format", format);
+
+        let response = self.authorize(self.http.post(url)).json(request).send()?;
+
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            let status = response.status().as_u16();
+            let body = Self::read_body(response)?;
+
+            Err(ClientError::server_error(status, &body))
+        }
+    }
+
+    pub fn events(&self) -> ClientResult<Events> {
+        let url = self.url("api/v1/events")?;
+        let http = HttpClient::builder().timeout(None).build()?;
+        let builder = self.authorize(http.get(url)).header(ACCEPT, "text/event-stream");
+        let response = builder.send()?;
+
+        if response.status().is_success() {
+            Ok(Events::new(response))
+        } else {
+            let status = response.status().as_u16();
+            let body = Self::read_body(response)?;
+
+            Err(ClientError::server_error(status, &body))
+        }
+    }
+
+    fn authorize(&self, builder: RequestBuilder) -> RequestBuilder {
+        match self.token {
+            Some(ref token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    fn url(&self, path: &str) -> ClientResult<Url> {
+        Ok(self.base_url.join(path)?)
+    }
+
+    fn servers_url(&self, segments: &[&str]) -> ClientResult<Url> {
+        let mut url = self.url("api/v1/servers/")?;
+        url.path_segments_mut()
+            .map_err(|_| ClientError::UrlError {
+                message: "Base URL can not be a base".into(),
+            })?
+            .pop_if_empty()
+            .extend(segments);
+
+        Ok(url)
+    }
+
+    fn get<T>(&self, url: Url) -> ClientResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.send(self.http.get(url))
+    }
+
+    fn send<T>(&self, builder: RequestBuilder) -> ClientResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self.authorize(builder).send()?;
+        let status = response.status();
+        let body = Self::read_body(response)?;
+
+        if status.is_success() {
+            Ok(serde_json::from_str(&body)?)
+        } else {
+            let message = serde_json::from_str::<StatusResponse>(&body)
+                .ok()
+                .and_then(|response| response.message().clone())
+                .unwrap_or(body);
+
+            Err(ClientError::server_error(status.as_u16(), &message))
+        }
+    }
+
+    fn read_body(response: Response) -> ClientResult<String> {
+        response.text().map_err(|err| ClientError::RequestError {
+            message: format!("{}", err),
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use reqwest::Error as ReqwestError;
+use serde_json::Error as JsonError;
+use url::ParseError as UrlError;
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+#[derive(Debug)]
+pub enum ClientError {
+    UrlError { message: String },
+    RequestError { message: String },
+    DecodeError { message: String },
+    ServerError { status: u16, message: String },
+}
+
+impl ClientError {
+    pub fn server_error(status: u16, message: &str) -> ClientError {
+        ClientError::ServerError {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            ClientError::UrlError { ref message } => write!(f, "{}", message),
+            ClientError::RequestError { ref message } => write!(f, "{}", message),
+            ClientError::DecodeError { ref message } => write!(f, "{}", message),
+            ClientError::ServerError {
+                status,
+                ref message,
+            } => write!(f, "{}: {}", status, message),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn description(&self) -> &str {
+        match *self {
+            ClientError::UrlError { .. } => "URL error",
+            ClientError::RequestError { .. } => "Request error",
+            ClientError::DecodeError { .. } => "Decode error",
+            ClientError::ServerError { .. } => "Server error",
+        }
+    }
+}
+
+impl From<UrlError> for ClientError {
+    fn from(error: UrlError) -> ClientError {
+        ClientError::UrlError {
+            message: format!("{}", error),
+        }
+    }
+}
+
+impl From<ReqwestError> for ClientError {
+    fn from(error: ReqwestError) -> ClientError {
+        if error.is_decode() {
+            ClientError::DecodeError {
+                message: format!("{}", error),
+            }
+        } else {
+            ClientError::RequestError {
+                message: format!("{}", error),
+            }
+        }
+    }
+}
+
+impl From<JsonError> for ClientError {
+    fn from(error: JsonError) -> ClientError {
+        ClientError::DecodeError {
+            message: format!("{}", error),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::io::BufRead;
+use std::io::BufReader;
+
+use reqwest::blocking::Response;
+
+use database_monitor_api::Event;
+
+use super::ClientError;
+use super::ClientResult;
+
+pub struct Events {
+    reader: BufReader<Response>,
+}
+
+impl Events {
+    pub fn new(response: Response) -> Events {
+        Events {
+            reader: BufReader::new(response),
+        }
+    }
+
+    fn read_message(&mut self) -> ClientResult<Option<String>> {
+        let mut data: Option<String> = None;
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+
+            let read = self.reader
+                .read_line(&mut line)
+                .map_err(|err| ClientError::RequestError {
+                    message: format!("{}", err),
+                })?;
+
+            if read == 0 {
+                return Ok(None);
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                if data.is_some() {
+                    return Ok(data);
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                let value = value.strip_prefix(' ').unwrap_or(value);
+
+                match data {
+                    Some(ref mut data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => data = Some(value.into()),
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for Events {
+    type Item = ClientResult<Event>;
+
+    fn next(&mut self) -> Option<ClientResult<Event>> {
+        match self.read_message() {
+            Ok(Some(data)) => Some(serde_json::from_str(&data).map_err(ClientError::from)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+extern crate database_monitor_api;
+extern crate reqwest;
+extern crate serde;
+extern crate serde_json;
+extern crate url;
+
+mod client;
+mod error;
+mod events;
+
+pub use database_monitor_api::Database;
+pub use database_monitor_api::DatabasesRequest;
+pub use database_monitor_api::DatabasesResponse;
+pub use database_monitor_api::DropDatabaseResponse;
+pub use database_monitor_api::Event;
+pub use database_monitor_api::Role;
+pub use database_monitor_api::Server;
+pub use database_monitor_api::ServerDetails;
+pub use database_monitor_api::ServerDetailsResponse;
+pub use database_monitor_api::ServersResponse;
+pub use database_monitor_api::StatusResponse;
+pub use database_monitor_api::WalSender;
+
+pub use self::client::Client;
+pub use self::error::ClientError;
+pub use self::error::ClientResult;
+pub use self::events::Events;
@@ -2,12 +2,19 @@
 
 /*globals requirejs */
 
+// Path prefix when the monitor is mounted below the site root
+const BASE_PATH = document.querySelector("meta[name=base-path]").getAttribute("content");
+
+function apiUrl(path) {
+  return BASE_PATH + "/api/v1" + path;
+}
+
 requirejs.config({
-  baseUrl: "/public/js/",
+  baseUrl: BASE_PATH + "/public/js/",
   paths: {
-    knockout: "https://cdnjs.cloudflare.com/ajax/libs/knockout/3.4.2/knockout-min",
-    moment: "https://cdnjs.cloudflare.com/ajax/libs/moment.js/2.19.1/moment.min",
-    reqwest: "https://cdnjs.cloudflare.com/ajax/libs/reqwest/2.0.5/reqwest.min",
+    knockout: "../vendor/knockout-min",
+    moment: "../vendor/moment.min",
+    reqwest: "../vendor/reqwest.min",
   },
   shim: {
     reqwest: {
@@ -22,8 +29,18 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
   const WARNING_TIMEOUT = 15 * 60;
   const ERROR_TIMEOUT = 30 * 60;
   const NO_DATA_MESSAGE = "No data";
+  const RESUBMIT_DELAY = 1000;
+  const MESSAGE_DELAY = 5 * 1000;
+
+  function errorMessage(err, fallback) {
+    try {
+      return JSON.parse(err.responseText)["message"] || fallback;
+    } catch (e) {
+      return fallback;
+    }
+  }
 
-  function DatabaseItem(_name, _collate, _role, _server, _description, _commit, _branch, _project, _updated) {
+  function DatabaseItem(_name, _collate, _role, _server, _description, _commit, _branch, _project, _updated, _flags, _age, _replicas) {
     this.name = ko.observable(_name);
     this.collate = ko.observable(_collate);
     this.role = ko.observable(_role);
@@ -33,6 +50,25 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
     this.branch = ko.observable(_branch);
     this.project = ko.observable(_project);
     this.updated = ko.observable(_updated);
+    this.flags = ko.observableArray(_flags);
+    this.age = ko.observable(_age);
+    this.replicas = ko.observableArray(_replicas);
+
+    this.hasReplicas = ko.pureComputed(function() {
+      return this.replicas().length > 0;
+    }, this);
+
+    this.replicasText = ko.pureComputed(function() {
+      return this.replicas().join(", ");
+    }, this);
+
+    this.hasAge = ko.pureComputed(function() {
+      return this.age() !== null;
+    }, this);
+
+    this.ageText = ko.pureComputed(function() {
+      return moment.duration(this.age(), "seconds").humanize();
+    }, this);
 
     this.hasDescription = ko.pureComputed(function() {
       if (this.description()) {
@@ -93,11 +129,18 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
 
     self.updated = ko.observable(0);
     self.query = ko.observable("");
+    self.sort = ko.observable("weight");
+    self.includeReplicas = ko.observable(false);
     self.loading = ko.observable(false);
     self.databases = ko.observableArray([]);
     self.message = ko.observable(NO_DATA_MESSAGE);
+    self.refreshing = ko.observable(false);
+    self.refreshMessage = ko.observable("");
+    self.managedServers = ko.observable({});
+    self.serverHealth = ko.observable({});
 
     self.timerId = null;
+    self.resubmitTimerId = null;
 
     self.lastUpdate = ko.computed(function () {
       var updated = self.updated();
@@ -130,10 +173,10 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
     });
 
     self.submit = function() {
-      var data = { "query": self.query() };
+      var data = { "query": self.query(), "sort": self.sort(), "include_replicas": self.includeReplicas() };
 
       reqwest({
-        url: "/api/v1/databases",
+        url: apiUrl("/databases"),
         method: "post",
         data: JSON.stringify(data),
         type: "json",
@@ -152,7 +195,10 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
               item["commit"] || 0,
               item["branch_name"] || "",
               item["project_name"] || "",
-              item["last_update"] || 0
+              item["last_update"] || 0,
+              item["flags"] || [],
+              item.hasOwnProperty("age") ? item["age"] : null,
+              item["replicas"] || []
             );
           }));
 
@@ -173,9 +219,147 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
       self.loading(true);
     };
 
+    self.download = function(format) {
+      var data = { "query": self.query(), "include_replicas": self.includeReplicas() };
+      var request = new XMLHttpRequest();
+
+      request.open("post", apiUrl("/databases/export") + "?format=" + format + "&sort=" + encodeURIComponent(self.sort()));
+      request.setRequestHeader("Content-Type", "application/json");
+      request.responseType = "blob";
+      request.onload = function() {
+        if (request.status === 200) {
+          var link = document.createElement("a");
+
+          link.href = window.URL.createObjectURL(request.response);
+          link.download = "databases." + format;
+          document.body.appendChild(link);
+          link.click();
+          document.body.removeChild(link);
+          window.URL.revokeObjectURL(link.href);
+        } else {
+          self.message("Failed to export databases");
+        }
+      };
+      request.send(JSON.stringify(data));
+    };
+
+    self.downloadCsv = function() {
+      self.download("csv");
+    };
+
+    self.downloadJsonLines = function() {
+      self.download("jsonl");
+    };
+
+    self.downloadJson = function() {
+      self.download("json");
+    };
+
+    self.refresh = function(url, name) {
+      self.refreshing(true);
+
+      reqwest({
+        url: url,
+        method: "post",
+        type: "json",
+      }).then(function (resp) {
+        self.refreshing(false);
+        self.refreshMessage("Refresh of " + name + " started");
+
+        window.setTimeout(function() {
+          self.refreshMessage("");
+        }, MESSAGE_DELAY);
+      }).fail(function(err) {
+        var resp = {};
+
+        try {
+          resp = JSON.parse(err.responseText);
+        } catch (e) {
+          resp = {};
+        }
+
+        self.refreshing(false);
+
+        if (resp["retry_after"]) {
+          self.refreshMessage("Refresh of " + name + " is possible in " + resp["retry_after"] + " seconds");
+        } else {
+          self.refreshMessage(resp["message"] || "Failed to refresh " + name);
+        }
+      });
+    };
+
+    self.refreshAll = function() {
+      self.refresh(apiUrl("/servers/refresh"), "all servers");
+    };
+
+    self.refreshServer = function(item) {
+      var server = item.server();
+
+      self.refresh(apiUrl("/servers/") + encodeURIComponent(server) + "/refresh", server);
+    };
+
+    self.isServerDown = function(server) {
+      return self.serverHealth()[server] === false;
+    };
+
+    self.isManaged = function(server) {
+      return self.managedServers()[server] === true;
+    };
+
+    self.loadServers = function() {
+      reqwest({
+        url: apiUrl("/servers"),
+        method: "get",
+        type: "json",
+      }).then(function (resp) {
+        var managed = {};
+
+        (resp["servers"] || []).forEach(function (server) {
+          managed[server["server_name"]] = server["management"] === true;
+        });
+
+        self.managedServers(managed);
+      });
+    };
+
+    self.dropDatabase = function(item) {
+      var server = item.server();
+      var name = item.name();
+      var url = apiUrl("/servers/") + encodeURIComponent(server) + "/databases/" + encodeURIComponent(name);
+
+      reqwest({
+        url: url + "?dry_run=true",
+        method: "delete",
+        type: "json",
+      }).then(function (plan) {
+        var question = "Drop database " + name + " on " + server + "?";
+
+        if (plan["backends"] > 0) {
+          question += " " + plan["backends"] + " active connections will be terminated.";
+        }
+
+        if (!window.confirm(question)) {
+          return;
+        }
+
+        reqwest({
+          url: url + "?confirm=" + encodeURIComponent(plan["confirm"]),
+          method: "delete",
+          type: "json",
+        }).then(function () {
+          self.databases.remove(item);
+          self.refreshMessage("Database " + name + " dropped");
+        }).fail(function(err) {
+          self.refreshMessage(errorMessage(err, "Failed to drop " + name));
+        });
+      }).fail(function(err) {
+        self.refreshMessage(errorMessage(err, "Failed to drop " + name));
+      });
+    };
+
     self.checkStatus = function () {
       reqwest({
-        url: "/api/v1/status",
+        url: apiUrl("/status"),
         method: "post",
       }).then(function (resp) {
         if (resp["ok"] === true) {
@@ -190,6 +374,66 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
       });
     };
 
+    self.resubmit = function() {
+      if (self.query().length === 0) {
+        return;
+      }
+
+      if (self.resubmitTimerId !== null) {
+        window.clearTimeout(self.resubmitTimerId);
+      }
+
+      self.resubmitTimerId = window.setTimeout(function() {
+        self.resubmitTimerId = null;
+        self.submit();
+      }, RESUBMIT_DELAY);
+    };
+
+    self.removeDatabase = function(server, name) {
+      self.databases.remove(function(item) {
+        return item.server() === server && item.name() === name;
+      });
+    };
+
+    self.setServerHealth = function(server, healthy) {
+      var health = Object.assign({}, self.serverHealth());
+
+      health[server] = healthy;
+      self.serverHealth(health);
+    };
+
+    self.listen = function() {
+      var source = new EventSource(apiUrl("/events"));
+
+      source.addEventListener("refresh_finished", function(message) {
+        var event = JSON.parse(message.data);
+
+        self.updated(event["last_update"] || 0);
+        self.resubmit();
+      });
+
+      source.addEventListener("database_created", function() {
+        self.resubmit();
+      });
+
+      source.addEventListener("database_dropped", function(message) {
+        var event = JSON.parse(message.data);
+
+        self.removeDatabase(event["server_name"], event["database_name"]);
+      });
+
+      source.addEventListener("server_health", function(message) {
+        var event = JSON.parse(message.data);
+
+        self.setServerHealth(event["server_name"], event["healthy"] === true);
+      });
+
+      // Events sent while disconnected are lost, catch up after reconnect
+      source.addEventListener("open", function() {
+        self.checkStatus();
+      });
+    };
+
     self.query.subscribe(function() {
       if (self.timerId !== null) {
         window.clearTimeout(self.timerId);
@@ -198,11 +442,24 @@ requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
       self.timerId = window.setTimeout(self.submit, 300);
     });
 
-    // Update status every 3 minutes
-    window.setInterval(self.checkStatus, 30 * 1000);
+    self.sort.subscribe(function() {
+      self.submit();
+    });
+
+    self.includeReplicas.subscribe(function() {
+      self.submit();
+    });
+
+    if (window.EventSource) {
+      self.listen();
+    } else {
+      // Poll status when the browser can't receive events
+      window.setInterval(self.checkStatus, 30 * 1000);
+    }
 
     // Update status after page loaded
     self.checkStatus();
+    self.loadServers();
   }
 
   ko.applyBindings(new SearchDatabaseModel());
@@ -0,0 +1,150 @@
+"use strict";
+
+/*globals requirejs */
+
+// Path prefix when the monitor is mounted below the site root
+const BASE_PATH = document.querySelector("meta[name=base-path]").getAttribute("content");
+
+function apiUrl(path) {
+  return BASE_PATH + "/api/v1" + path;
+}
+
+requirejs.config({
+  baseUrl: BASE_PATH + "/public/js/",
+  paths: {
+    knockout: "../vendor/knockout-min",
+    moment: "../vendor/moment.min",
+    reqwest: "../vendor/reqwest.min",
+  },
+  shim: {
+    reqwest: {
+      exports: "reqwest"
+    },
+  },
+  waitSeconds: 15,
+});
+
+// Start the application logic.
+requirejs([ "knockout", "moment", "reqwest" ], function(ko, moment, reqwest) {
+  const NO_DATA_MESSAGE = "No data";
+  const UNKNOWN = "unknown";
+  const SIZE_UNITS = [ "B", "kB", "MB", "GB", "TB" ];
+
+  function formatSize(size) {
+    var unit = 0;
+
+    while (size >= 1024 && unit < SIZE_UNITS.length - 1) {
+      size /= 1024;
+      unit += 1;
+    }
+
+    return size.toFixed(unit === 0 ? 0 : 1) + " " + SIZE_UNITS[unit];
+  }
+
+  function ServerItem(server) {
+    this.name = server["server_name"];
+    this.description = server["server_description"] || "";
+    this.databases = server["databases"];
+    this.version = server["version"] || UNKNOWN;
+    this.maxConnections = server.hasOwnProperty("max_connections") ? server["max_connections"] : UNKNOWN;
+    this.roles = server["roles"] || [];
+
+    if (server.hasOwnProperty("start_time")) {
+      this.uptime = moment.unix(server["start_time"]).fromNow(true);
+    } else {
+      this.uptime = UNKNOWN;
+    }
+
+    this.replicas = server["replicas"] || [];
+    this.walSenders = (server["wal_senders"] || []).map(function (sender) {
+      return {
+        name: sender["application_name"],
+        address: sender["client_addr"] || "",
+        state: sender["state"],
+        lagText: sender.hasOwnProperty("lag_bytes") ? formatSize(sender["lag_bytes"]) : UNKNOWN
+      };
+    });
+
+    if (server.hasOwnProperty("in_recovery")) {
+      this.recoveryText = server["in_recovery"] ? "standby" : "primary";
+    } else {
+      this.recoveryText = UNKNOWN;
+    }
+
+    if (server.hasOwnProperty("primary")) {
+      this.recoveryText += " of " + server["primary"];
+    }
+
+    if (server.hasOwnProperty("replication_lag")) {
+      this.recoveryText += ", lag " + moment.duration(server["replication_lag"], "seconds").humanize();
+    }
+
+    if (server.hasOwnProperty("cluster_size")) {
+      this.sizeText = formatSize(server["cluster_size"]);
+    } else {
+      this.sizeText = UNKNOWN;
+    }
+  }
+
+  function ServersModel() {
+    var self = this;
+
+    self.loading = ko.observable(false);
+    self.servers = ko.observableArray([]);
+    self.message = ko.observable("");
+
+    self.loadServer = function(name) {
+      return reqwest({
+        url: apiUrl("/servers/") + encodeURIComponent(name),
+        method: "get",
+        type: "json",
+      }).then(function (resp) {
+        if (resp["ok"] === true) {
+          self.servers.push(new ServerItem(resp["server"]));
+          self.servers.sort(function (a, b) {
+            return a.name < b.name ? -1 : (a.name > b.name ? 1 : 0);
+          });
+        }
+      });
+    };
+
+    self.load = function() {
+      self.loading(true);
+      self.message("");
+
+      reqwest({
+        url: apiUrl("/servers"),
+        method: "get",
+        type: "json",
+      }).then(function (resp) {
+        var servers = resp["servers"] || [];
+
+        self.servers([]);
+        self.loading(false);
+
+        if (servers.length === 0) {
+          self.message(NO_DATA_MESSAGE);
+        }
+
+        servers.forEach(function (server) {
+          self.loadServer(server["server_name"]);
+        });
+      }).fail(function() {
+        self.loading(false);
+        self.message("Failed to load servers");
+      });
+    };
+
+    self.load();
+  }
+
+  ko.applyBindings(new ServersModel());
+}, function (err) {
+  console.log(err.requireType);
+
+  if (err.requireType === "timeout") {
+    console.log("modules: " + err.requireModules);
+  }
+
+  throw err;
+});
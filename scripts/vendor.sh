@@ -0,0 +1,67 @@
+#!/bin/sh
+# Downloads third-party front-end libraries into public/vendor, they are
+# embedded into the binary on the next build. Every file must match its
+# checksum in scripts/vendor.sha256, nothing is installed otherwise.
+#
+# DBMON_VENDOR_CDN, DBMON_VENDOR_DIR and DBMON_VENDOR_CHECKSUMS override the
+# download location, the target directory and the checksum file.
+
+set -eu
+
+CDN="${DBMON_VENDOR_CDN:-https://cdnjs.cloudflare.com/ajax/libs}"
+SEMANTIC_FONTS="themes/default/assets/fonts"
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+VENDOR="${DBMON_VENDOR_DIR:-$ROOT/public/vendor}"
+CHECKSUMS="${DBMON_VENDOR_CHECKSUMS:-$ROOT/scripts/vendor.sha256}"
+DOWNLOAD="$(mktemp -d)"
+
+trap 'rm -rf "$DOWNLOAD"' EXIT
+
+fetch() {
+    mkdir -p "$(dirname "$DOWNLOAD/$2")"
+    curl -fsSL -o "$DOWNLOAD/$2" "$CDN/$1"
+}
+
+check() {
+    actual="$(sha256sum "$DOWNLOAD/$1" | cut -d ' ' -f 1)"
+    expected="$(awk -v name="$1" '$2 == name { print $1 }' "$CHECKSUMS")"
+
+    if [ -z "$expected" ]; then
+        echo "No checksum for $1, verify the file and add it to $CHECKSUMS:" >&2
+        echo "$actual  $1" >&2
+        return 1
+    fi
+
+    if [ "$actual" != "$expected" ]; then
+        echo "Checksum mismatch for $1: $actual, expected $expected" >&2
+        return 1
+    fi
+}
+
+FILES="require.min.js knockout-min.js moment.min.js reqwest.min.js semantic-ui/semantic.css"
+
+fetch require.js/2.3.5/require.min.js require.min.js
+fetch knockout/3.4.2/knockout-min.js knockout-min.js
+fetch moment.js/2.19.1/moment.min.js moment.min.js
+fetch reqwest/2.0.5/reqwest.min.js reqwest.min.js
+fetch semantic-ui/2.2.7/semantic.css semantic-ui/semantic.css
+
+for font in icons.eot icons.svg icons.ttf icons.woff icons.woff2; do
+    fetch "semantic-ui/2.2.7/$SEMANTIC_FONTS/$font" "semantic-ui/$SEMANTIC_FONTS/$font"
+    FILES="$FILES semantic-ui/$SEMANTIC_FONTS/$font"
+done
+
+FAILED=0
+
+for file in $FILES; do
+    check "$file" || FAILED=1
+done
+
+if [ "$FAILED" -ne 0 ]; then
+    echo "Nothing installed into $VENDOR" >&2
+    exit 1
+fi
+
+rm -rf "$VENDOR"
+mkdir -p "$(dirname "$VENDOR")"
+cp -R "$DOWNLOAD" "$VENDOR"
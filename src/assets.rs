@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+#[derive(Debug, Clone, Default)]
+pub struct Assets {
+    directory: Option<PathBuf>,
+}
+
+fn is_relative(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+impl Assets {
+    pub fn new(directory: Option<PathBuf>) -> Assets {
+        Assets { directory }
+    }
+
+    pub fn directory(&self) -> &Option<PathBuf> {
+        &self.directory
+    }
+
+    pub fn get(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        if !is_relative(path) {
+            return None;
+        }
+
+        match self.directory {
+            Some(ref directory) => match fs::read(directory.join(path)) {
+                Ok(content) => Some(Cow::Owned(content)),
+                Err(err) => {
+                    debug!("Failed to read asset {}: {}", path, err);
+
+                    None
+                }
+            },
+            None => EMBEDDED
+                .binary_search_by(|(name, _)| (*name).cmp(path))
+                .ok()
+                .map(|index| Cow::Borrowed(EMBEDDED[index].1)),
+        }
+    }
+}
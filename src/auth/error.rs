@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FtmResult;
+
+#[derive(Debug)]
+pub enum AuthError {
+    MissingCredentials,
+    InvalidCredentials { user: Option<String> },
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut Formatter) -> FtmResult {
+        match *self {
+            AuthError::MissingCredentials => write!(f, "Credentials required"),
+            AuthError::InvalidCredentials { user: Some(ref user) } => {
+                write!(f, "Invalid credentials for user {}", user)
+            }
+            AuthError::InvalidCredentials { user: None } => write!(f, "Invalid credentials"),
+        }
+    }
+}
+
+impl Error for AuthError {
+    fn description(&self) -> &str {
+        match *self {
+            AuthError::MissingCredentials => "Credentials required",
+            AuthError::InvalidCredentials { .. } => "Invalid credentials",
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Result as IoResult;
+use std::path::Path;
+
+use sha1::Digest;
+use sha1::Sha1;
+
+#[derive(Debug)]
+pub struct Htpasswd {
+    entries: HashMap<String, String>,
+}
+
+impl Htpasswd {
+    pub fn read_from_file<P>(path: P) -> IoResult<Htpasswd>
+    where
+        P: AsRef<Path>,
+    {
+        let file = BufReader::new(File::open(path)?);
+        let mut entries = HashMap::new();
+
+        for line in file.lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.find(':') {
+                Some(index) => {
+                    let (user, hash) = (&line[..index], &line[index + 1..]);
+
+                    if hash.starts_with("{SHA}") {
+                        warn!(
+                            "htpasswd user {} has unsalted SHA-1 hash, \
+                             rehash the password with bcrypt (htpasswd -B)",
+                            user
+                        );
+                    } else if !is_bcrypt(hash) {
+                        warn!("Unsupported htpasswd hash format for user {}", user);
+                    }
+
+                    entries.insert(user.into(), hash.into());
+                }
+                None => warn!("Skipping invalid htpasswd line for {}", line),
+            }
+        }
+
+        Ok(Htpasswd { entries })
+    }
+
+    pub fn verify(&self, user: &str, password: &str) -> bool {
+        let hash = match self.entries.get(user) {
+            Some(hash) => hash,
+            None => return false,
+        };
+
+        if is_bcrypt(hash) {
+            bcrypt::verify(password, hash).unwrap_or(false)
+        } else if let Some(expected) = hash.strip_prefix("{SHA}") {
+            let digest = base64::encode(Sha1::digest(password.as_bytes()));
+
+            constant_time_eq(digest.as_bytes(), expected.as_bytes())
+        } else {
+            false
+        }
+    }
+}
+
+fn is_bcrypt(hash: &str) -> bool {
+    hash.starts_with("$2y$") || hash.starts_with("$2a$") || hash.starts_with("$2b$")
+}
+
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0, |result, (x, y)| result | (x ^ y)) == 0
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Result as IoResult;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract;
+use axum::extract::ConnectInfo;
+use axum::extract::Request;
+use axum::http::header;
+use axum::http::HeaderMap;
+use axum::http::HeaderValue;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response;
+
+use crate::config::AuthConfig;
+use crate::config::DefaultVisibility;
+use crate::state::Visibility;
+
+use super::htpasswd::constant_time_eq;
+use super::AuthError;
+use super::AuthUser;
+use super::Htpasswd;
+use super::Permission;
+
+#[derive(Debug)]
+struct TrustedProxy {
+    header: String,
+    addresses: Vec<IpAddr>,
+}
+
+#[derive(Debug)]
+pub struct AuthMiddleware {
+    tokens: Vec<(String, String)>,
+    htpasswd: Option<Htpasswd>,
+    proxy: Option<TrustedProxy>,
+    visibility: HashMap<String, HashSet<String>>,
+    default_visibility: DefaultVisibility,
+    permissions: HashMap<String, Permission>,
+    default_permission: Permission,
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim())
+}
+
+impl AuthMiddleware {
+    pub fn new(config: &AuthConfig, trusted_proxies: &[IpAddr]) -> IoResult<AuthMiddleware> {
+        let tokens = config
+            .tokens()
+            .iter()
+            .map(|token| (token.token().clone(), token.user().clone()))
+            .collect();
+        let htpasswd = match config.htpasswd() {
+            Some(path) => Some(Htpasswd::read_from_file(path)?),
+            None => None,
+        };
+        let proxy = config.proxy().as_ref().map(|proxy| TrustedProxy {
+            header: proxy.header().clone(),
+            addresses: trusted_proxies.to_vec(),
+        });
+        let mut visibility: HashMap<String, HashSet<String>> = HashMap::new();
+
+        for rule in config.visibility() {
+            visibility
+                .entry(rule.user().clone())
+                .or_default()
+                .extend(rule.servers().iter().cloned());
+        }
+
+        let mut permissions: HashMap<String, Permission> = HashMap::new();
+
+        for rule in config.permissions() {
+            let permission = permissions.entry(rule.user().clone()).or_default();
+            *permission = (*permission).max(rule.permission());
+        }
+
+        Ok(AuthMiddleware {
+            tokens,
+            htpasswd,
+            proxy,
+            visibility,
+            default_visibility: config.default_visibility(),
+            permissions,
+            default_permission: config.default_permission(),
+        })
+    }
+
+    fn authenticate_proxy(&self, headers: &HeaderMap, address: IpAddr) -> Option<String> {
+        let proxy = self.proxy.as_ref()?;
+
+        if !proxy.addresses.contains(&address) {
+            return None;
+        }
+
+        header_value(headers, &proxy.header)
+            .filter(|user| !user.is_empty())
+            .map(|user| user.into())
+    }
+
+    fn authenticate_token(&self, token: &str) -> Result<String, AuthError> {
+        for (expected, user) in &self.tokens {
+            if constant_time_eq(expected.as_bytes(), token.as_bytes()) {
+                return Ok(user.clone());
+            }
+        }
+
+        Err(AuthError::InvalidCredentials { user: None })
+    }
+
+    fn authenticate_basic(&self, credentials: &str) -> Result<String, AuthError> {
+        let htpasswd = match self.htpasswd {
+            Some(ref htpasswd) => htpasswd,
+            None => return Err(AuthError::InvalidCredentials { user: None }),
+        };
+        let decoded = base64::decode(credentials)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or(AuthError::InvalidCredentials { user: None })?;
+        let index = decoded
+            .find(':')
+            .ok_or(AuthError::InvalidCredentials { user: None })?;
+        let (user, password) = (&decoded[..index], &decoded[index + 1..]);
+
+        if htpasswd.verify(user, password) {
+            Ok(user.into())
+        } else {
+            Err(AuthError::InvalidCredentials {
+                user: Some(user.into()),
+            })
+        }
+    }
+
+    fn authenticate(&self, headers: &HeaderMap, address: IpAddr) -> Result<String, AuthError> {
+        if let Some(user) = self.authenticate_proxy(headers, address) {
+            return Ok(user);
+        }
+
+        match header_value(headers, "Authorization") {
+            Some(value) if value.starts_with("Bearer ") => {
+                self.authenticate_token(value[7..].trim())
+            }
+            Some(value) if value.starts_with("Basic ") => {
+                self.authenticate_basic(value[6..].trim())
+            }
+            Some(_) => Err(AuthError::InvalidCredentials { user: None }),
+            None => Err(AuthError::MissingCredentials),
+        }
+    }
+
+    fn user(&self, name: &str) -> AuthUser {
+        let visibility = match (self.visibility.get(name), self.default_visibility) {
+            (Some(servers), _) => Visibility::Servers(servers.clone()),
+            (None, DefaultVisibility::All) => Visibility::All,
+            (None, DefaultVisibility::None) => Visibility::Servers(HashSet::new()),
+        };
+        let permission = self
+            .permissions
+            .get(name)
+            .cloned()
+            .unwrap_or(self.default_permission);
+
+        AuthUser::new(name, visibility, permission)
+    }
+
+    fn unauthorized(&self) -> Response {
+        let mut response = (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+
+        if self.htpasswd.is_some() {
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"Database monitor\""),
+            );
+        }
+
+        response
+    }
+
+    pub async fn handle(
+        extract::State(auth): extract::State<Arc<Self>>,
+        ConnectInfo(address): ConnectInfo<SocketAddr>,
+        mut request: Request,
+        next: Next,
+    ) -> Response {
+        match auth.authenticate(request.headers(), address.ip()) {
+            Ok(name) => {
+                debug!("Authenticated user {}", name);
+
+                let user = auth.user(&name);
+                request.extensions_mut().insert(user);
+
+                next.run(request).await
+            }
+            Err(err) => {
+                warn!("Authentication failed from {}: {}", address, err);
+
+                auth.unauthorized()
+            }
+        }
+    }
+}
@@ -0,0 +1,16 @@
+mod error;
+mod htpasswd;
+mod middleware;
+mod permission;
+mod user;
+
+use self::error::AuthError;
+use self::htpasswd::Htpasswd;
+
+pub use self::htpasswd::constant_time_eq;
+pub use self::middleware::AuthMiddleware;
+pub use self::permission::Permission;
+pub use self::user::permission;
+pub use self::user::user_name;
+pub use self::user::visibility;
+pub use self::user::AuthUser;
@@ -0,0 +1,14 @@
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    #[default]
+    Read,
+    Refresh,
+    Admin,
+}
+
+impl Permission {
+    pub fn allows(self, required: Permission) -> bool {
+        self >= required
+    }
+}
@@ -0,0 +1,54 @@
+use axum::http::Extensions;
+
+use crate::state::Visibility;
+
+use super::Permission;
+
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    name: String,
+    visibility: Visibility,
+    permission: Permission,
+}
+
+impl AuthUser {
+    pub fn new(name: &str, visibility: Visibility, permission: Permission) -> AuthUser {
+        AuthUser {
+            name: name.into(),
+            visibility,
+            permission,
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+
+    pub fn permission(&self) -> Permission {
+        self.permission
+    }
+}
+
+pub fn visibility(extensions: &Extensions) -> Visibility {
+    match extensions.get::<AuthUser>() {
+        Some(user) => user.visibility().clone(),
+        None => Visibility::All,
+    }
+}
+
+pub fn permission(extensions: &Extensions) -> Permission {
+    match extensions.get::<AuthUser>() {
+        Some(user) => user.permission(),
+        None => Permission::Admin,
+    }
+}
+
+pub fn user_name(extensions: &Extensions) -> Option<String> {
+    extensions
+        .get::<AuthUser>()
+        .map(|user| user.name().clone())
+}
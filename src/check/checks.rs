@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::stdout;
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use futures::future;
+use tokio::time;
+use tokio_postgres::types::FromSql;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::types::Type;
+use tokio_postgres::Statement;
+
+use crate::config::Arguments;
+use crate::config::Configuration;
+use crate::config::MetadataConnInfo;
+use crate::config::ServerConnInfo;
+use crate::worker::prepare_metadata_query;
+use crate::worker::Connector;
+use crate::worker::DefaultConnector;
+use crate::worker::WorkerResult;
+
+use super::CheckItem;
+use super::CheckReport;
+
+const CHECK_TIMEOUT: u64 = 10;
+const METADATA_ITEM: &str = "metadata query";
+const METADATA_COLUMNS: &[&str] = &["commit", "branch", "project", "created"];
+const TRUSTED_PROXIES_ITEM: &str = "trusted proxies";
+
+async fn with_timeout<F, T>(check: F) -> Result<T, String>
+where
+    F: Future<Output = WorkerResult<T>>,
+{
+    match time::timeout(Duration::from_secs(CHECK_TIMEOUT), check).await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err(format!("No response in {} seconds", CHECK_TIMEOUT)),
+    }
+}
+
+fn check_duplicates(servers: &[ServerConnInfo], report: &mut CheckReport) {
+    let mut entries: HashMap<&String, Vec<usize>> = HashMap::new();
+
+    for (index, server) in servers.iter().enumerate() {
+        entries.entry(server.host()).or_default().push(index + 1);
+    }
+
+    for server in servers {
+        let indexes = match entries.remove(server.host()) {
+            Some(indexes) => indexes,
+            None => continue,
+        };
+        let name = format!("server {}", server.host());
+
+        if indexes.len() > 1 {
+            let indexes: Vec<String> = indexes.iter().map(|index| index.to_string()).collect();
+
+            report.push(CheckItem::error(
+                &name,
+                &format!(
+                    "defined {} times (entries {} of servers)",
+                    indexes.len(),
+                    indexes.join(", ")
+                ),
+            ));
+        }
+    }
+}
+
+fn check_primaries(servers: &[ServerConnInfo], report: &mut CheckReport) {
+    let hosts: HashSet<&String> = servers.iter().map(|server| server.host()).collect();
+
+    for server in servers {
+        if let Some(ref primary) = *server.primary() {
+            let name = format!("server {}", server.host());
+
+            if primary == server.host() {
+                report.push(CheckItem::error(&name, "primary refers to the server itself"));
+            } else if !hosts.contains(primary) {
+                report.push(CheckItem::error(
+                    &name,
+                    &format!("primary {} is not defined in servers", primary),
+                ));
+            }
+        }
+    }
+}
+
+fn check_parameters(params: &[Type], report: &mut CheckReport) -> bool {
+    if params.len() != 2 {
+        report.push(CheckItem::error(
+            METADATA_ITEM,
+            &format!(
+                "expected 2 parameters ($1 server name, $2 database name), found {}",
+                params.len()
+            ),
+        ));
+
+        return false;
+    }
+
+    let mut valid = true;
+
+    for (index, param) in params.iter().enumerate() {
+        if !<&str as ToSql>::accepts(param) {
+            report.push(CheckItem::error(
+                METADATA_ITEM,
+                &format!("parameter ${} has type {}, expected text", index + 1, param),
+            ));
+
+            valid = false;
+        }
+    }
+
+    valid
+}
+
+fn check_columns(statement: &Statement, report: &mut CheckReport) -> bool {
+    let columns = statement.columns();
+
+    if columns.len() < 3 {
+        report.push(CheckItem::error(
+            METADATA_ITEM,
+            &format!(
+                "expected at least 3 columns (commit, branch, project), found {}",
+                columns.len()
+            ),
+        ));
+
+        return false;
+    }
+
+    let mut valid = true;
+
+    for (index, column) in columns.iter().enumerate().take(METADATA_COLUMNS.len()) {
+        let (accepts, expected) = match index {
+            1 | 2 => (<String as FromSql>::accepts(column.type_()), "text"),
+            _ => (<i64 as FromSql>::accepts(column.type_()), "bigint"),
+        };
+
+        if !accepts {
+            report.push(CheckItem::error(
+                METADATA_ITEM,
+                &format!(
+                    "column {} ({}) has type {}, expected {}",
+                    index + 1,
+                    METADATA_COLUMNS[index],
+                    column.type_(),
+                    expected
+                ),
+            ));
+
+            valid = false;
+        }
+    }
+
+    if columns.len() > METADATA_COLUMNS.len() {
+        report.push(CheckItem::warning(
+            METADATA_ITEM,
+            &format!(
+                "{} extra columns are ignored",
+                columns.len() - METADATA_COLUMNS.len()
+            ),
+        ));
+    }
+
+    valid
+}
+
+async fn check_metadata(metadata: &Option<MetadataConnInfo>, report: &mut CheckReport) {
+    let metadata = match *metadata {
+        Some(ref metadata) => metadata,
+        None => {
+            report.push(CheckItem::skipped(METADATA_ITEM, "metadata is not configured"));
+
+            return;
+        }
+    };
+
+    let statement = match with_timeout(prepare_metadata_query(metadata)).await {
+        Ok(statement) => statement,
+        Err(err) => {
+            report.push(CheckItem::error(
+                METADATA_ITEM,
+                &format!(
+                    "failed to prepare on {}:{}/{}: {}",
+                    metadata.host(),
+                    metadata.port(),
+                    metadata.database(),
+                    err
+                ),
+            ));
+
+            return;
+        }
+    };
+
+    let params_valid = check_parameters(statement.params(), report);
+    let columns_valid = check_columns(&statement, report);
+
+    if params_valid && columns_valid {
+        let columns: Vec<String> = statement
+            .columns()
+            .iter()
+            .map(|column| format!("{} {}", column.name(), column.type_()))
+            .collect();
+
+        report.push(CheckItem::ok(
+            METADATA_ITEM,
+            &format!("returns {}", columns.join(", ")),
+        ));
+    }
+}
+
+async fn check_connections(
+    connector: &dyn Connector,
+    servers: &[ServerConnInfo],
+    report: &mut CheckReport,
+) {
+    let checks = servers
+        .iter()
+        .map(|server| {
+            with_timeout(connector.probe_server(server, Duration::from_secs(CHECK_TIMEOUT)))
+        });
+    let results = future::join_all(checks).await;
+
+    for (server, result) in servers.iter().zip(results) {
+        let name = format!("connection {}:{}", server.host(), server.port());
+
+        match result {
+            Ok((details, databases)) => report.push(CheckItem::ok(
+                &name,
+                &format!(
+                    "version {}, {} databases",
+                    details.version(),
+                    databases.len()
+                ),
+            )),
+            Err(err) => report.push(CheckItem::error(&name, &err)),
+        }
+    }
+}
+
+fn check_management(config: &Configuration, report: &mut CheckReport) {
+    if config.management().is_none() {
+        return;
+    }
+
+    if config.auth().is_none() {
+        report.push(CheckItem::error("management", "management mode requires auth section"));
+    } else {
+        report.push(CheckItem::ok("management", "management mode is enabled"));
+    }
+}
+
+fn check_trusted_proxies(config: &Configuration, report: &mut CheckReport) {
+    let proxy_auth = config
+        .auth()
+        .as_ref()
+        .is_some_and(|auth| auth.proxy().is_some());
+
+    match config.trusted_proxies() {
+        Ok(addresses) if addresses.is_empty() => {
+            if proxy_auth {
+                report.push(CheckItem::warning(
+                    TRUSTED_PROXIES_ITEM,
+                    "proxy authentication is configured, but trusted_proxies is empty",
+                ));
+            }
+        }
+        Ok(addresses) => report.push(CheckItem::ok(
+            TRUSTED_PROXIES_ITEM,
+            &format!("{} trusted proxies", addresses.len()),
+        )),
+        Err(err) => report.push(CheckItem::error(TRUSTED_PROXIES_ITEM, &err.to_string())),
+    }
+}
+
+fn check_readable_file(name: &str, path: &str, report: &mut CheckReport) {
+    let result = File::open(path).and_then(|file| file.metadata());
+
+    match result {
+        Ok(metadata) if metadata.is_file() => {
+            report.push(CheckItem::ok(name, &format!("{} is readable", path)));
+        }
+        Ok(_) => report.push(CheckItem::error(name, &format!("{} is not a file", path))),
+        Err(err) => report.push(CheckItem::error(name, &format!("{}: {}", path, err))),
+    }
+}
+
+fn check_directory(name: &str, path: &Path, report: &mut CheckReport) {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.is_dir() => {
+            report.push(CheckItem::ok(name, &format!("{} is a directory", path.display())));
+        }
+        Ok(_) => report.push(CheckItem::error(
+            name,
+            &format!("{} is not a directory", path.display()),
+        )),
+        Err(err) => report.push(CheckItem::error(
+            name,
+            &format!("{}: {}", path.display(), err),
+        )),
+    }
+}
+
+fn check_writable_file(name: &str, path: &str, report: &mut CheckReport) {
+    let file = Path::new(path);
+
+    if !file.exists() {
+        let directory = match file.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory,
+            _ => Path::new("."),
+        };
+
+        check_directory(name, directory, report);
+
+        return;
+    }
+
+    match OpenOptions::new().append(true).open(file) {
+        Ok(_) => report.push(CheckItem::ok(name, &format!("{} is writable", path))),
+        Err(err) => report.push(CheckItem::error(name, &format!("{}: {}", path, err))),
+    }
+}
+
+fn check_files(config: &Configuration, report: &mut CheckReport) {
+    if let Some(tls) = config.tls() {
+        check_readable_file("tls certificate", tls.certificate(), report);
+        check_readable_file("tls key", tls.key(), report);
+    }
+
+    if let Some(htpasswd) = config.auth().as_ref().and_then(|auth| auth.htpasswd().as_ref()) {
+        check_readable_file("htpasswd", htpasswd, report);
+    }
+
+    let audit_log = config
+        .management()
+        .as_ref()
+        .and_then(|management| management.audit_log().as_ref());
+
+    if let Some(audit_log) = audit_log {
+        check_writable_file("audit log", audit_log, report);
+    }
+
+    if let Some(state_file) = config.state_file() {
+        check_writable_file("state file", state_file, report);
+    }
+
+    if let Some(assets_dir) = config.assets_dir() {
+        check_directory("assets directory", Path::new(assets_dir), report);
+    }
+}
+
+pub async fn check_configuration(
+    config: &Configuration,
+    connector: &dyn Connector,
+    connections: bool,
+) -> CheckReport {
+    let mut report = CheckReport::new();
+    let servers = config.servers();
+
+    if servers.is_empty() {
+        report.push(CheckItem::warning("servers", "no servers configured"));
+    } else {
+        report.push(CheckItem::ok(
+            "servers",
+            &format!("{} servers configured", servers.len()),
+        ));
+    }
+
+    check_duplicates(servers, &mut report);
+    check_primaries(servers, &mut report);
+    check_management(config, &mut report);
+    check_trusted_proxies(config, &mut report);
+    check_files(config, &mut report);
+    check_metadata(config.metadata(), &mut report).await;
+
+    if connections {
+        check_connections(connector, servers, &mut report).await;
+    } else if !servers.is_empty() {
+        report.push(CheckItem::skipped(
+            "connections",
+            "use --check-connections to connect to every server",
+        ));
+    }
+
+    report
+}
+
+pub async fn run<I>(arguments: &Arguments, environment: I) -> i32
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let path = arguments.config_file().clone().unwrap_or_default();
+    let mut report = CheckReport::new();
+
+    match Configuration::load(arguments, environment) {
+        Ok(config) => {
+            let connections = arguments.check_connections();
+
+            report.push(CheckItem::ok("configuration", &format!("{} is valid", path)));
+            report.append(check_configuration(&config, &DefaultConnector, connections).await);
+        }
+        Err(err) => {
+            report.push(CheckItem::error("configuration", &format!("{}: {}", path, err)));
+        }
+    }
+
+    let stdout = stdout();
+    let mut writer = stdout.lock();
+
+    if let Err(err) = report.write(&mut writer).and_then(|_| writer.flush()) {
+        error!("Failed to write report: {}", err);
+
+        return 2;
+    }
+
+    if report.is_ok() {
+        0
+    } else {
+        1
+    }
+}
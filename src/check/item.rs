@@ -0,0 +1,46 @@
+use super::CheckStatus;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckItem {
+    name: String,
+    status: CheckStatus,
+    message: String,
+}
+
+impl CheckItem {
+    pub fn new(name: &str, status: CheckStatus, message: &str) -> CheckItem {
+        CheckItem {
+            name: name.into(),
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn ok(name: &str, message: &str) -> CheckItem {
+        CheckItem::new(name, CheckStatus::Ok, message)
+    }
+
+    pub fn warning(name: &str, message: &str) -> CheckItem {
+        CheckItem::new(name, CheckStatus::Warning, message)
+    }
+
+    pub fn error(name: &str, message: &str) -> CheckItem {
+        CheckItem::new(name, CheckStatus::Error, message)
+    }
+
+    pub fn skipped(name: &str, message: &str) -> CheckItem {
+        CheckItem::new(name, CheckStatus::Skipped, message)
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn status(&self) -> CheckStatus {
+        self.status
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
@@ -0,0 +1,10 @@
+mod checks;
+mod item;
+mod report;
+mod status;
+
+pub use self::checks::check_configuration;
+pub use self::checks::run;
+pub use self::item::CheckItem;
+pub use self::report::CheckReport;
+pub use self::status::CheckStatus;
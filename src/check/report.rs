@@ -0,0 +1,53 @@
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use super::CheckItem;
+use super::CheckStatus;
+
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    items: Vec<CheckItem>,
+}
+
+impl CheckReport {
+    pub fn new() -> CheckReport {
+        CheckReport::default()
+    }
+
+    pub fn push(&mut self, item: CheckItem) {
+        self.items.push(item);
+    }
+
+    pub fn append(&mut self, other: CheckReport) {
+        self.items.extend(other.items);
+    }
+
+    pub fn items(&self) -> &Vec<CheckItem> {
+        &self.items
+    }
+
+    pub fn errors(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status() == CheckStatus::Error)
+            .count()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors() == 0
+    }
+
+    pub fn write(&self, writer: &mut dyn Write) -> IoResult<()> {
+        for item in &self.items {
+            writeln!(
+                writer,
+                "{:<8} {}: {}",
+                item.status(),
+                item.name(),
+                item.message()
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+    Skipped,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let label = match *self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warning => "warning",
+            CheckStatus::Error => "error",
+            CheckStatus::Skipped => "skipped",
+        };
+
+        f.pad(label)
+    }
+}
@@ -1,22 +1,72 @@
+use std::env;
+use std::ffi::OsStr;
 use std::fs::File;
+use std::io;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
 use std::io::Read;
 use std::io::Result as IoResult;
+use std::net::IpAddr;
 use std::path::Path;
+use std::process;
+use std::str::FromStr;
 
-use serde_json;
+use serde::de::Error as DeError;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde_json::Map;
+use serde_json::Value;
 
 use argparse::ArgumentParser;
+use argparse::Store;
 use argparse::StoreOption;
+use argparse::StoreTrue;
+
+use crate::auth::Permission;
+use crate::export::ExportFormat;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerKind {
+    #[default]
+    Postgres,
+    Mysql,
+}
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct ServerConnInfo {
+    #[serde(default)] kind: ServerKind,
     host: String,
     port: Option<u16>,
     description: Option<String>,
     role: String,
     password: String,
+    interval: Option<u64>,
+    quiet_hours: Option<QuietHours>,
+    management: Option<ManagementRole>,
+    primary: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ManagementRole {
+    role: String,
+    password: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeOfDay {
+    minutes: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BasePath {
+    path: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct QuietHours {
+    from: TimeOfDay,
+    to: TimeOfDay,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -29,24 +79,162 @@ pub struct MetadataConnInfo {
     query: String,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct TokenInfo {
+    user: String,
+    token: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProxyInfo {
+    header: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct VisibilityRule {
+    user: String,
+    servers: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PermissionRule {
+    user: String,
+    permission: Permission,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultVisibility {
+    All,
+    #[default]
+    None,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AuthConfig {
+    #[serde(default)] tokens: Vec<TokenInfo>,
+    htpasswd: Option<String>,
+    proxy: Option<ProxyInfo>,
+    #[serde(default)] visibility: Vec<VisibilityRule>,
+    #[serde(default)] default_visibility: DefaultVisibility,
+    #[serde(default)] permissions: Vec<PermissionRule>,
+    #[serde(default)] default_permission: Permission,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ManagementConfig {
+    #[serde(default)] protected: Vec<String>,
+    audit_log: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RetentionRule {
+    flag: String,
+    #[serde(default)] no_metadata: bool,
+    idle_days: Option<u64>,
+    min_age_days: Option<u64>,
+    owner: Option<String>,
+    name: Option<String>,
+    server: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TlsConfig {
+    certificate: String,
+    key: String,
+    redirect_port: Option<u16>,
+    redirect_host: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OnceOptions {
+    output: Option<String>,
+    format: ExportFormat,
+}
+
+#[derive(Debug, Clone)]
+pub struct Arguments {
+    address: Option<String>,
+    port: Option<u16>,
+    interval: Option<u64>,
+    config_file: Option<String>,
+    once: bool,
+    output: Option<String>,
+    format: ExportFormat,
+    assets_dir: Option<String>,
+    check_config: bool,
+    check_connections: bool,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Configuration {
     address: String,
     port: u16,
     interval: u64,
+    probe_timeout: Option<u64>,
     metadata: Option<MetadataConnInfo>,
     servers: Vec<ServerConnInfo>,
+    auth: Option<AuthConfig>,
+    tls: Option<TlsConfig>,
+    #[serde(default)] trusted_proxies: Vec<String>,
+    management: Option<ManagementConfig>,
+    state_file: Option<String>,
+    #[serde(default)] retention: Vec<RetentionRule>,
+    #[serde(default)] base_path: BasePath,
+    #[serde(skip)] once: Option<OnceOptions>,
+    #[serde(skip)] assets_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnvValue {
+    String,
+    Number,
+    List,
+}
+
+const ENV_PREFIX: &str = "DBMON_";
+
+const ENV_OVERRIDES: &[(&str, &[&str], EnvValue)] = &[
+    ("ADDRESS", &["address"], EnvValue::String),
+    ("PORT", &["port"], EnvValue::Number),
+    ("INTERVAL", &["interval"], EnvValue::Number),
+    ("PROBE_TIMEOUT", &["probe_timeout"], EnvValue::Number),
+    ("BASE_PATH", &["base_path"], EnvValue::String),
+    ("STATE_FILE", &["state_file"], EnvValue::String),
+    ("TRUSTED_PROXIES", &["trusted_proxies"], EnvValue::List),
+    ("METADATA_HOST", &["metadata", "host"], EnvValue::String),
+    ("METADATA_PORT", &["metadata", "port"], EnvValue::Number),
+    ("METADATA_DATABASE", &["metadata", "database"], EnvValue::String),
+    ("METADATA_ROLE", &["metadata", "role"], EnvValue::String),
+    ("METADATA_PASSWORD", &["metadata", "password"], EnvValue::String),
+    ("METADATA_QUERY", &["metadata", "query"], EnvValue::String),
+];
+
+const DEFAULT_PROBE_TIMEOUT: u64 = 30;
 const DEFAULT_PORT: u16 = 5432;
+const DEFAULT_MYSQL_PORT: u16 = 3306;
 
 impl ServerConnInfo {
+    pub fn kind(&self) -> ServerKind {
+        self.kind
+    }
+
     pub fn host(&self) -> &String {
         &self.host
     }
 
     pub fn port(&self) -> u16 {
-        self.port.unwrap_or(DEFAULT_PORT)
+        match self.kind {
+            ServerKind::Postgres => self.port.unwrap_or(DEFAULT_PORT),
+            ServerKind::Mysql => self.port.unwrap_or(DEFAULT_MYSQL_PORT),
+        }
     }
 
     pub fn description(&self) -> &Option<String> {
@@ -60,6 +248,230 @@ impl ServerConnInfo {
     pub fn password(&self) -> &String {
         &self.password
     }
+
+    pub fn interval(&self) -> Option<u64> {
+        self.interval
+    }
+
+    pub fn quiet_hours(&self) -> &Option<QuietHours> {
+        &self.quiet_hours
+    }
+
+    pub fn management(&self) -> &Option<ManagementRole> {
+        &self.management
+    }
+
+    pub fn primary(&self) -> &Option<String> {
+        &self.primary
+    }
+}
+
+impl ManagementRole {
+    pub fn role(&self) -> &String {
+        &self.role
+    }
+
+    pub fn password(&self) -> &String {
+        &self.password
+    }
+}
+
+impl TimeOfDay {
+    pub fn new(hour: u32, minute: u32) -> TimeOfDay {
+        TimeOfDay {
+            minutes: hour * 60 + minute,
+        }
+    }
+
+    pub fn now() -> TimeOfDay {
+        let now = time::now();
+
+        TimeOfDay::new(now.tm_hour as u32, now.tm_min as u32)
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<TimeOfDay, String> {
+        let mut parts = value.splitn(2, ':');
+        let hour = parts.next().and_then(|hour| hour.parse::<u32>().ok());
+        let minute = parts.next().and_then(|minute| minute.parse::<u32>().ok());
+
+        match (hour, minute) {
+            (Some(hour), Some(minute)) if hour < 24 && minute < 60 => {
+                Ok(TimeOfDay::new(hour, minute))
+            }
+            _ => Err(format!("Invalid time of day {}, expected HH:MM", value)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeOfDay {
+    fn deserialize<D>(deserializer: D) -> Result<TimeOfDay, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(DeError::custom)
+    }
+}
+
+impl ConfigFormat {
+    pub fn from_path<P>(path: P) -> ConfigFormat
+    where
+        P: AsRef<Path>,
+    {
+        match path.as_ref().extension().and_then(OsStr::to_str) {
+            Some("toml") => ConfigFormat::Toml,
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            _ => ConfigFormat::Json,
+        }
+    }
+
+    pub fn parse(self, raw: &str) -> Result<Value, String> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(raw).map_err(|err| err.to_string()),
+            ConfigFormat::Toml => toml::from_str(raw).map_err(|err| err.to_string()),
+            ConfigFormat::Yaml => serde_yaml::from_str(raw).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+fn env_value(name: &str, value: String, kind: EnvValue) -> IoResult<Value> {
+    match kind {
+        EnvValue::String => Ok(Value::String(value)),
+        EnvValue::Number => value.trim().parse::<u64>().map(Value::from).map_err(|err| {
+            IoError::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid value of {}: {}", name, err),
+            )
+        }),
+        EnvValue::List => Ok(Value::Array(
+            value
+                .split(',')
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.into()))
+                .collect(),
+        )),
+    }
+}
+
+fn set_value(root: &mut Value, path: &[&str], value: Value) -> IoResult<()> {
+    let (last, parents) = path.split_last().unwrap();
+    let mut current = root;
+
+    for key in parents {
+        current = current
+            .as_object_mut()
+            .ok_or_else(|| IoError::new(ErrorKind::InvalidData, "Configuration is not an object"))?
+            .entry(*key)
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+
+    match current.as_object_mut() {
+        Some(object) => {
+            object.insert((*last).to_string(), value);
+
+            Ok(())
+        }
+        None => Err(IoError::new(
+            ErrorKind::InvalidData,
+            format!("Configuration field {} is not an object", parents.join(".")),
+        )),
+    }
+}
+
+fn apply_env_overrides<I>(root: &mut Value, environment: I) -> IoResult<()>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    for (name, value) in environment {
+        let key = match name.strip_prefix(ENV_PREFIX) {
+            Some(key) => key,
+            None => continue,
+        };
+
+        match ENV_OVERRIDES.iter().find(|(env_key, _, _)| *env_key == key) {
+            Some((_, path, kind)) => {
+                debug!("Overriding {} from {}", path.join("."), name);
+
+                set_value(root, path, env_value(&name, value, *kind)?)?;
+            }
+            None => warn!("Ignoring unknown environment variable {}", name),
+        }
+    }
+
+    Ok(())
+}
+
+impl BasePath {
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    pub fn join(&self, path: &str) -> String {
+        format!("{}{}", self.path, path)
+    }
+}
+
+fn is_path_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment != "."
+        && segment != ".."
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c))
+}
+
+impl FromStr for BasePath {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<BasePath, String> {
+        let trimmed = value.trim_matches('/');
+
+        if trimmed.is_empty() {
+            return Ok(BasePath::default());
+        }
+
+        if !trimmed.split('/').all(is_path_segment) {
+            return Err(format!(
+                "Invalid base path {}, expected segments of letters, digits, '-', '.', '_' or '~'",
+                value
+            ));
+        }
+
+        Ok(BasePath {
+            path: format!("/{}", trimmed),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for BasePath {
+    fn deserialize<D>(deserializer: D) -> Result<BasePath, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(DeError::custom)
+    }
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: TimeOfDay) -> bool {
+        if self.from <= self.to {
+            self.from <= time && time < self.to
+        } else {
+            self.from <= time || time < self.to
+        }
+    }
 }
 
 impl MetadataConnInfo {
@@ -88,42 +500,250 @@ impl MetadataConnInfo {
     }
 }
 
-impl Configuration {
-    pub fn from_args() -> IoResult<Configuration> {
-        let mut address: Option<String> = None;
-        let mut port: Option<u16> = None;
-        let mut interval: Option<u64> = None;
-        let mut config_file: Option<String> = None;
+impl TokenInfo {
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn token(&self) -> &String {
+        &self.token
+    }
+}
+
+impl ProxyInfo {
+    pub fn header(&self) -> &String {
+        &self.header
+    }
+
+}
+
+impl VisibilityRule {
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn servers(&self) -> &Vec<String> {
+        &self.servers
+    }
+}
+
+impl PermissionRule {
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn permission(&self) -> Permission {
+        self.permission
+    }
+}
+
+impl AuthConfig {
+    pub fn tokens(&self) -> &Vec<TokenInfo> {
+        &self.tokens
+    }
+
+    pub fn htpasswd(&self) -> &Option<String> {
+        &self.htpasswd
+    }
+
+    pub fn proxy(&self) -> &Option<ProxyInfo> {
+        &self.proxy
+    }
+
+    pub fn visibility(&self) -> &Vec<VisibilityRule> {
+        &self.visibility
+    }
+
+    pub fn default_visibility(&self) -> DefaultVisibility {
+        self.default_visibility
+    }
+
+    pub fn permissions(&self) -> &Vec<PermissionRule> {
+        &self.permissions
+    }
+
+    pub fn default_permission(&self) -> Permission {
+        self.default_permission
+    }
+}
+
+impl ManagementConfig {
+    pub fn protected(&self) -> &Vec<String> {
+        &self.protected
+    }
+
+    pub fn audit_log(&self) -> &Option<String> {
+        &self.audit_log
+    }
+}
+
+impl RetentionRule {
+    pub fn flag(&self) -> &String {
+        &self.flag
+    }
+
+    pub fn no_metadata(&self) -> bool {
+        self.no_metadata
+    }
+
+    pub fn idle_days(&self) -> Option<u64> {
+        self.idle_days
+    }
+
+    pub fn min_age_days(&self) -> Option<u64> {
+        self.min_age_days
+    }
+
+    pub fn owner(&self) -> &Option<String> {
+        &self.owner
+    }
+
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    pub fn server(&self) -> &Option<String> {
+        &self.server
+    }
+}
+
+impl TlsConfig {
+    pub fn certificate(&self) -> &String {
+        &self.certificate
+    }
+
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    pub fn redirect_port(&self) -> Option<u16> {
+        self.redirect_port
+    }
+
+    pub fn redirect_host(&self) -> &Option<String> {
+        &self.redirect_host
+    }
+}
+
+impl OnceOptions {
+    pub fn output(&self) -> &Option<String> {
+        &self.output
+    }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+}
+
+impl Arguments {
+    pub fn from_args() -> Arguments {
+        Self::parse(env::args().collect())
+    }
+
+    pub fn parse(args: Vec<String>) -> Arguments {
+        let mut arguments = Arguments {
+            address: None,
+            port: None,
+            interval: None,
+            config_file: None,
+            once: false,
+            output: None,
+            format: ExportFormat::Json,
+            assets_dir: None,
+            check_config: false,
+            check_connections: false,
+        };
 
         {
             let mut ap = ArgumentParser::new();
 
             ap.set_description("PostgreSQL database monitor.");
-            ap.refer(&mut address).add_option(
+            ap.refer(&mut arguments.address).add_option(
                 &["-b", "--bind"],
                 StoreOption,
                 "Address to bind on (default: localhost)",
             );
-            ap.refer(&mut port).add_option(
+            ap.refer(&mut arguments.port).add_option(
                 &["-p", "--port"],
                 StoreOption,
                 "Port to listen (default: 8080)",
             );
-            ap.refer(&mut interval).add_option(
+            ap.refer(&mut arguments.interval).add_option(
                 &["-i", "--interval"],
                 StoreOption,
                 "Probe interval in seconds (default: 600)",
             );
-            ap.refer(&mut config_file).add_option(
+            ap.refer(&mut arguments.config_file).add_option(
                 &["-c", "--config"],
                 StoreOption,
                 "Path to configuration file",
             );
-            ap.parse_args_or_exit();
+            ap.refer(&mut arguments.once).add_option(
+                &["--once"],
+                StoreTrue,
+                "Probe all servers once, print databases and exit",
+            );
+            ap.refer(&mut arguments.output).add_option(
+                &["-o", "--output"],
+                StoreOption,
+                "Output file for --once mode (default: stdout)",
+            );
+            ap.refer(&mut arguments.format).add_option(
+                &["-f", "--format"],
+                Store,
+                "Output format for --once mode: json, jsonl or csv (default: json)",
+            );
+            ap.refer(&mut arguments.assets_dir).add_option(
+                &["--assets-dir"],
+                StoreOption,
+                "Serve templates and public files from this directory instead of embedded copies",
+            );
+            ap.refer(&mut arguments.check_config).add_option(
+                &["--check-config"],
+                StoreTrue,
+                "Validate configuration, print a report and exit",
+            );
+            ap.refer(&mut arguments.check_connections).add_option(
+                &["--check-connections"],
+                StoreTrue,
+                "Also connect to every server in --check-config mode",
+            );
+
+            if let Err(code) = ap.parse(args, &mut io::stdout(), &mut io::stderr()) {
+                process::exit(code);
+            }
         }
 
-        let mut config = match config_file {
-            Some(config_file) => Self::read_from_file(config_file)?,
+        arguments
+    }
+
+    pub fn config_file(&self) -> &Option<String> {
+        &self.config_file
+    }
+
+    pub fn check_config(&self) -> bool {
+        self.check_config
+    }
+
+    pub fn check_connections(&self) -> bool {
+        self.check_connections
+    }
+}
+
+impl Configuration {
+    pub fn from_args_and_env<I>(args: Vec<String>, environment: I) -> IoResult<Configuration>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        Self::load(&Arguments::parse(args), environment)
+    }
+
+    pub fn load<I>(arguments: &Arguments, environment: I) -> IoResult<Configuration>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut config = match arguments.config_file {
+            Some(ref config_file) => Self::read_from_file(config_file, environment)?,
             None => {
                 error!("Configuration file path is required");
 
@@ -134,36 +754,55 @@ impl Configuration {
             }
         };
 
-        if let Some(address) = address {
-            config.address = address;
+        if let Some(ref address) = arguments.address {
+            config.address = address.clone();
         }
 
-        if let Some(port) = port {
+        if let Some(port) = arguments.port {
             config.port = port;
         }
 
-        if let Some(interval) = interval {
+        if let Some(interval) = arguments.interval {
             config.interval = interval;
         }
 
+        if arguments.once {
+            config.once = Some(OnceOptions {
+                output: arguments.output.clone(),
+                format: arguments.format,
+            });
+        }
+
+        config.assets_dir = arguments.assets_dir.clone();
+
         Ok(config)
     }
 
-    fn read_from_file<P>(path: P) -> IoResult<Configuration>
+    fn read_from_file<P, I>(path: P, environment: I) -> IoResult<Configuration>
     where
         P: AsRef<Path>,
+        I: IntoIterator<Item = (String, String)>,
     {
+        let format = ConfigFormat::from_path(&path);
         let mut file = File::open(path)?;
         let mut raw = String::new();
 
         file.read_to_string(&mut raw)?;
 
-        match serde_json::from_str(&raw) {
+        let mut value = format.parse(&raw).map_err(|err| {
+            error!("Failed to parse configuration file: {}", err);
+
+            IoError::new(ErrorKind::InvalidData, err)
+        })?;
+
+        apply_env_overrides(&mut value, environment)?;
+
+        match serde_path_to_error::deserialize(value) {
             Ok(config) => Ok(config),
             Err(err) => {
-                error!("Failed to parse configuration file: {}", err);
+                error!("Invalid configuration: {}", err);
 
-                Err(IoError::new(ErrorKind::Other, err))
+                Err(IoError::other(err))
             }
         }
     }
@@ -180,6 +819,10 @@ impl Configuration {
         self.interval
     }
 
+    pub fn probe_timeout(&self) -> u64 {
+        self.probe_timeout.unwrap_or(DEFAULT_PROBE_TIMEOUT)
+    }
+
     pub fn metadata(&self) -> &Option<MetadataConnInfo> {
         &self.metadata
     }
@@ -187,6 +830,52 @@ impl Configuration {
     pub fn servers(&self) -> &Vec<ServerConnInfo> {
         &self.servers
     }
+
+    pub fn auth(&self) -> &Option<AuthConfig> {
+        &self.auth
+    }
+
+    pub fn tls(&self) -> &Option<TlsConfig> {
+        &self.tls
+    }
+
+    pub fn trusted_proxies(&self) -> IoResult<Vec<IpAddr>> {
+        self.trusted_proxies
+            .iter()
+            .map(|address| {
+                address.parse().map_err(|err| {
+                    IoError::new(
+                        ErrorKind::InvalidInput,
+                        format!("Invalid trusted proxy address {}: {}", address, err),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    pub fn management(&self) -> &Option<ManagementConfig> {
+        &self.management
+    }
+
+    pub fn state_file(&self) -> &Option<String> {
+        &self.state_file
+    }
+
+    pub fn retention(&self) -> &Vec<RetentionRule> {
+        &self.retention
+    }
+
+    pub fn base_path(&self) -> &BasePath {
+        &self.base_path
+    }
+
+    pub fn once(&self) -> &Option<OnceOptions> {
+        &self.once
+    }
+
+    pub fn assets_dir(&self) -> &Option<String> {
+        &self.assets_dir
+    }
 }
 
 impl Default for Configuration {
@@ -195,8 +884,18 @@ impl Default for Configuration {
             address: "localhost".into(),
             port: 8080,
             interval: 600,
+            probe_timeout: None,
             metadata: None,
             servers: Vec::new(),
+            auth: None,
+            tls: None,
+            trusted_proxies: Vec::new(),
+            management: None,
+            state_file: None,
+            retention: Vec::new(),
+            base_path: BasePath::default(),
+            once: None,
+            assets_dir: None,
         }
     }
 }
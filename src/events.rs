@@ -0,0 +1,36 @@
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::Sender;
+
+use database_monitor_api::Event;
+
+const CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: Sender<Event>,
+}
+
+impl EventBus {
+    pub fn new() -> EventBus {
+        let (sender, _) = broadcast::channel(CAPACITY);
+
+        EventBus { sender }
+    }
+
+    pub fn publish(&self, event: Event) {
+        debug!("Publishing {} event", event.name());
+
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> Receiver<Event> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        EventBus::new()
+    }
+}
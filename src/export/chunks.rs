@@ -0,0 +1,63 @@
+use std::cmp;
+use std::io::Result as IoResult;
+
+use crate::state::DatabaseRow;
+
+use super::writer::write_footer;
+use super::writer::write_header;
+use super::writer::write_records;
+use super::ExportFormat;
+
+const CHUNK_ROWS: usize = 256;
+
+#[derive(Debug)]
+pub struct ExportChunks {
+    format: ExportFormat,
+    rows: Vec<DatabaseRow>,
+    offset: Option<usize>,
+    finished: bool,
+}
+
+impl ExportChunks {
+    pub fn new(format: ExportFormat, rows: Vec<DatabaseRow>) -> ExportChunks {
+        ExportChunks {
+            format,
+            rows,
+            offset: None,
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for ExportChunks {
+    type Item = IoResult<Vec<u8>>;
+
+    fn next(&mut self) -> Option<IoResult<Vec<u8>>> {
+        if self.finished {
+            return None;
+        }
+
+        let mut chunk = Vec::new();
+        let result = match self.offset {
+            None => {
+                self.offset = Some(0);
+
+                write_header(self.format, &mut chunk)
+            }
+            Some(offset) if offset < self.rows.len() => {
+                let end = cmp::min(offset + CHUNK_ROWS, self.rows.len());
+
+                self.offset = Some(end);
+
+                write_records(self.format, &self.rows[offset..end], offset, &mut chunk)
+            }
+            Some(_) => {
+                self.finished = true;
+
+                write_footer(self.format, &mut chunk)
+            }
+        };
+
+        Some(result.map(|_| chunk))
+    }
+}
@@ -0,0 +1,9 @@
+mod chunks;
+mod format;
+mod record;
+mod writer;
+
+pub use self::chunks::ExportChunks;
+pub use self::format::ExportFormat;
+pub use self::record::ExportRecord;
+pub use self::writer::write_rows;
@@ -0,0 +1,98 @@
+use crate::state::DatabaseRow;
+
+pub const LIST_SEPARATOR: &str = ";";
+
+pub const CSV_HEADER: &[&str] = &[
+    "server_name",
+    "server_description",
+    "database_name",
+    "collation_name",
+    "role_name",
+    "commit",
+    "branch_name",
+    "project_name",
+    "last_update",
+    "first_seen",
+    "created",
+    "flags",
+    "replicas",
+    "weight",
+];
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ExportRecord<'a> {
+    server_name: &'a str,
+    server_description: Option<&'a str>,
+    database_name: &'a str,
+    collation_name: &'a str,
+    role_name: &'a str,
+    commit: Option<i64>,
+    branch_name: Option<&'a str>,
+    project_name: Option<&'a str>,
+    last_update: i64,
+    first_seen: i64,
+    created: Option<i64>,
+    flags: &'a [String],
+    replicas: &'a [String],
+    weight: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CsvRecord<'a> {
+    server_name: &'a str,
+    server_description: Option<&'a str>,
+    database_name: &'a str,
+    collation_name: &'a str,
+    role_name: &'a str,
+    commit: Option<i64>,
+    branch_name: Option<&'a str>,
+    project_name: Option<&'a str>,
+    last_update: i64,
+    first_seen: i64,
+    created: Option<i64>,
+    flags: String,
+    replicas: String,
+    weight: usize,
+}
+
+impl<'a> From<&'a DatabaseRow> for ExportRecord<'a> {
+    fn from(row: &'a DatabaseRow) -> ExportRecord<'a> {
+        ExportRecord {
+            server_name: row.server_name(),
+            server_description: row.server_description().as_deref(),
+            database_name: row.database_name(),
+            collation_name: row.database_collate(),
+            role_name: row.database_owner(),
+            commit: *row.commit(),
+            branch_name: row.branch_name().as_deref(),
+            project_name: row.project_name().as_deref(),
+            last_update: row.last_update(),
+            first_seen: row.first_seen(),
+            created: row.created(),
+            flags: row.flags(),
+            replicas: row.replicas(),
+            weight: row.weight(),
+        }
+    }
+}
+
+impl<'a> From<ExportRecord<'a>> for CsvRecord<'a> {
+    fn from(record: ExportRecord<'a>) -> CsvRecord<'a> {
+        CsvRecord {
+            server_name: record.server_name,
+            server_description: record.server_description,
+            database_name: record.database_name,
+            collation_name: record.collation_name,
+            role_name: record.role_name,
+            commit: record.commit,
+            branch_name: record.branch_name,
+            project_name: record.project_name,
+            last_update: record.last_update,
+            first_seen: record.first_seen,
+            created: record.created,
+            flags: record.flags.join(LIST_SEPARATOR),
+            replicas: record.replicas.join(LIST_SEPARATOR),
+            weight: record.weight,
+        }
+    }
+}
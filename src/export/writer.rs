@@ -0,0 +1,88 @@
+use std::io::Error as IoError;
+use std::io::Result as IoResult;
+use std::io::Write;
+
+use csv::WriterBuilder;
+
+use crate::state::DatabaseRow;
+
+use super::record::CsvRecord;
+use super::record::CSV_HEADER;
+use super::ExportFormat;
+use super::ExportRecord;
+
+fn write_csv(rows: &[DatabaseRow], writer: &mut dyn Write) -> IoResult<()> {
+    let mut csv_writer = WriterBuilder::new().has_headers(false).from_writer(writer);
+
+    for row in rows {
+        csv_writer
+            .serialize(CsvRecord::from(ExportRecord::from(row)))
+            .map_err(IoError::other)?;
+    }
+
+    csv_writer.flush()
+}
+
+fn write_json_lines(rows: &[DatabaseRow], writer: &mut dyn Write) -> IoResult<()> {
+    for row in rows {
+        serde_json::to_writer(&mut *writer, &ExportRecord::from(row))?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+fn write_json(rows: &[DatabaseRow], offset: usize, writer: &mut dyn Write) -> IoResult<()> {
+    for (index, row) in rows.iter().enumerate() {
+        if offset + index > 0 {
+            writer.write_all(b",")?;
+        }
+
+        serde_json::to_writer(&mut *writer, &ExportRecord::from(row))?;
+    }
+
+    Ok(())
+}
+
+pub fn write_header(format: ExportFormat, writer: &mut dyn Write) -> IoResult<()> {
+    match format {
+        ExportFormat::Csv => {
+            let mut csv_writer = WriterBuilder::new().from_writer(writer);
+
+            csv_writer.write_record(CSV_HEADER).map_err(IoError::other)?;
+            csv_writer.flush()
+        }
+        ExportFormat::JsonLines => Ok(()),
+        ExportFormat::Json => writer.write_all(b"["),
+    }
+}
+
+pub fn write_records(
+    format: ExportFormat,
+    rows: &[DatabaseRow],
+    offset: usize,
+    writer: &mut dyn Write,
+) -> IoResult<()> {
+    match format {
+        ExportFormat::Csv => write_csv(rows, writer),
+        ExportFormat::JsonLines => write_json_lines(rows, writer),
+        ExportFormat::Json => write_json(rows, offset, writer),
+    }
+}
+
+pub fn write_footer(format: ExportFormat, writer: &mut dyn Write) -> IoResult<()> {
+    match format {
+        ExportFormat::Csv | ExportFormat::JsonLines => Ok(()),
+        ExportFormat::Json => writer.write_all(b"]"),
+    }
+}
+
+pub fn write_rows(
+    format: ExportFormat,
+    rows: &[DatabaseRow],
+    writer: &mut dyn Write,
+) -> IoResult<()> {
+    write_header(format, writer)?;
+    write_records(format, rows, 0, writer)?;
+    write_footer(format, writer)
+}
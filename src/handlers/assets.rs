@@ -0,0 +1,83 @@
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract;
+use axum::extract::ConnectInfo;
+use axum::http::header;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+
+use crate::assets::Assets;
+use crate::config::BasePath;
+
+use super::forwarded::Forwarded;
+
+const INDEX_TEMPLATE: &str = "template/index.html";
+const SERVERS_TEMPLATE: &str = "template/servers.html";
+const BASE_PATH_PLACEHOLDER: &str = "{{base_path}}";
+
+pub struct AssetsHandler {
+    assets: Assets,
+    base_path: BasePath,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl AssetsHandler {
+    pub fn new(assets: Assets, base_path: BasePath, trusted_proxies: Vec<IpAddr>) -> AssetsHandler {
+        AssetsHandler {
+            assets,
+            base_path,
+            trusted_proxies,
+        }
+    }
+
+    fn render(&self, path: &str, headers: &HeaderMap, remote_addr: SocketAddr) -> Response {
+        let template = match self.assets.get(path) {
+            Some(template) => template,
+            None => return StatusCode::NOT_FOUND.into_response(),
+        };
+        let forwarded = Forwarded::from_request(headers, remote_addr.ip(), &self.trusted_proxies);
+        let base_path = forwarded.base_path(&self.base_path);
+        let page =
+            String::from_utf8_lossy(&template).replace(BASE_PATH_PLACEHOLDER, base_path.as_str());
+
+        ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], page).into_response()
+    }
+
+    fn respond(&self, path: &str) -> Response {
+        match self.assets.get(path) {
+            Some(content) => {
+                let mime = mime_guess::from_path(path).first_or_octet_stream();
+
+                ([(header::CONTENT_TYPE, mime.to_string())], content).into_response()
+            }
+            None => StatusCode::NOT_FOUND.into_response(),
+        }
+    }
+
+    pub async fn handle_public(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extract::Path(path): extract::Path<String>,
+    ) -> Response {
+        handler.respond(&format!("public/{}", path))
+    }
+
+    pub async fn handle_index(
+        extract::State(handler): extract::State<Arc<Self>>,
+        ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+    ) -> Response {
+        handler.render(INDEX_TEMPLATE, &headers, remote_addr)
+    }
+
+    pub async fn handle_servers(
+        extract::State(handler): extract::State<Arc<Self>>,
+        ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+    ) -> Response {
+        handler.render(SERVERS_TEMPLATE, &headers, remote_addr)
+    }
+}
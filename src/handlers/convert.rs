@@ -0,0 +1,83 @@
+use database_monitor_api::Database;
+use database_monitor_api::Role;
+use database_monitor_api::Server;
+use database_monitor_api::ServerDetails;
+use database_monitor_api::WalSender;
+
+use crate::state::DatabaseRow;
+use crate::state::ServerInfo;
+use crate::state::ServerRow;
+
+pub fn database(row: &DatabaseRow) -> Database {
+    let now = time::get_time().sec;
+    let database = Database::new(
+        row.server_name(),
+        row.server_description(),
+        row.database_name(),
+        row.database_collate(),
+        row.database_owner(),
+    );
+
+    database
+        .with_metadata(row.commit(), row.branch_name(), row.project_name())
+        .with_last_update(row.last_update())
+        .with_age(
+            row.first_seen(),
+            row.created(),
+            now - row.created_or_first_seen(),
+        )
+        .with_flags(row.flags())
+        .with_replicas(row.replicas())
+}
+
+pub fn server(row: &ServerRow, management: bool) -> Server {
+    Server::new(row.name(), row.description(), row.n_databases(), management).with_replication(
+        row.primary(),
+        row.replication_lag(),
+        row.replicas(),
+    )
+}
+
+pub fn server_details(info: &ServerInfo, n_databases: usize, replicas: &[String]) -> ServerDetails {
+    let server = ServerDetails::new(info.name(), info.description(), n_databases);
+
+    match info.details() {
+        Some(details) => {
+            let roles = details
+                .roles()
+                .iter()
+                .map(|role| Role::new(role.name(), role.superuser(), role.login()))
+                .collect();
+            let wal_senders = details
+                .replicas()
+                .iter()
+                .map(|replica| {
+                    WalSender::new(
+                        replica.application_name(),
+                        replica.client_addr(),
+                        replica.state(),
+                        replica.lag_bytes(),
+                    )
+                })
+                .collect();
+
+            server
+                .with_inventory(
+                    details.version(),
+                    details.start_time(),
+                    details.in_recovery(),
+                    details.cluster_size(),
+                    details.max_connections(),
+                )
+                .with_roles(roles)
+                .with_last_update(info.last_update())
+                .with_replication(
+                    info.primary(),
+                    details.replication_lag(),
+                    replicas,
+                    wal_senders,
+                )
+        }
+        None => server,
+    }
+}
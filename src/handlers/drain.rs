@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract;
+use axum::extract::Request;
+use axum::http::header;
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response;
+
+use crate::shutdown::Shutdown;
+
+use super::guarded_body::GuardedBody;
+
+pub struct DrainHandler {
+    shutdown: Shutdown,
+}
+
+impl DrainHandler {
+    pub fn new(shutdown: Shutdown) -> DrainHandler {
+        DrainHandler { shutdown }
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        if handler.shutdown.is_requested() {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::CONNECTION, "close")],
+                "Server is shutting down",
+            )
+                .into_response();
+        }
+
+        let guard = handler.shutdown.begin_request();
+
+        next.run(request)
+            .await
+            .map(|body| Body::new(GuardedBody::new(body, guard)))
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract;
+use axum::extract::ConnectInfo;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::http::Extensions;
+use axum::http::StatusCode;
+
+use database_monitor_api::DropDatabaseResponse;
+
+use crate::auth::permission;
+use crate::auth::user_name;
+use crate::auth::visibility;
+use crate::auth::Permission;
+use crate::management::ManagementError;
+use crate::management::Manager;
+use crate::management::CONFIRMATION_TIMEOUT;
+use crate::state::State;
+
+use super::error::HandlerResult;
+use super::util::json_response;
+use super::util::query_flag;
+
+pub struct DropDatabaseHandler {
+    state: State,
+    manager: Option<Manager>,
+}
+
+impl DropDatabaseHandler {
+    pub fn new(state: State, manager: Option<Manager>) -> DropDatabaseHandler {
+        DropDatabaseHandler { state, manager }
+    }
+
+    fn error_status(error: &ManagementError) -> StatusCode {
+        match *error {
+            ManagementError::ServerNotManaged { .. } => StatusCode::FORBIDDEN,
+            ManagementError::ProtectedDatabase { .. } => StatusCode::FORBIDDEN,
+            ManagementError::DatabaseNotFound { .. } => StatusCode::NOT_FOUND,
+            ManagementError::ConfirmationRequired => StatusCode::PRECONDITION_FAILED,
+            ManagementError::DatabaseError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            ManagementError::AuditError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(error: ManagementError) -> HandlerResult {
+        let response = DropDatabaseResponse::err(&format!("{}", error));
+
+        json_response(Self::error_status(&error), &response)
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+        ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+        Path((server_name, database_name)): Path<(String, String)>,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> HandlerResult {
+        if !visibility(&extensions).is_visible(&server_name) {
+            let response = DropDatabaseResponse::err("Server not found");
+
+            return json_response(StatusCode::NOT_FOUND, &response);
+        }
+
+        if !permission(&extensions).allows(Permission::Admin) {
+            let response = DropDatabaseResponse::err("Management is not permitted");
+
+            return json_response(StatusCode::FORBIDDEN, &response);
+        }
+
+        let manager = match handler.manager {
+            Some(ref manager) => manager,
+            None => {
+                let response = DropDatabaseResponse::err("Management mode is disabled");
+
+                return json_response(StatusCode::FORBIDDEN, &response);
+            }
+        };
+        let user = match user_name(&extensions) {
+            Some(user) => user,
+            None => {
+                let response = DropDatabaseResponse::err("Management requires authentication");
+
+                return json_response(StatusCode::FORBIDDEN, &response);
+            }
+        };
+        let user = Some(user.as_str());
+        let address = format!("{}", remote_addr.ip());
+
+        if query_flag(&params, "dry_run") {
+            match manager
+                .plan(&server_name, &database_name, user, &address)
+                .await
+            {
+                Ok(plan) => {
+                    let response = DropDatabaseResponse::planned(
+                        plan.backends(),
+                        plan.confirm(),
+                        CONFIRMATION_TIMEOUT,
+                    );
+
+                    json_response(StatusCode::OK, &response)
+                }
+                Err(err) => Self::error_response(err),
+            }
+        } else {
+            let confirm = params.get("confirm").cloned().unwrap_or_default();
+
+            match manager
+                .drop_database(&server_name, &database_name, &confirm, user, &address)
+                .await
+            {
+                Ok(backends) => {
+                    handler.state.remove_database(&server_name, &database_name);
+
+                    json_response(StatusCode::OK, &DropDatabaseResponse::dropped(backends))
+                }
+                Err(err) => Self::error_response(err),
+            }
+        }
+    }
+}
@@ -1,19 +1,11 @@
-use iron::Handler;
-use iron::IronResult;
-use iron::Request;
-use iron::Response;
-use iron::status;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
 
 pub struct EmptyHandler;
 
 impl EmptyHandler {
-    pub fn new() -> EmptyHandler {
-        EmptyHandler {}
-    }
-}
-
-impl Handler for EmptyHandler {
-    fn handle(&self, _: &mut Request) -> IronResult<Response> {
-        Ok(Response::with((status::BadRequest, "No API entry point")))
+    pub async fn handle() -> Response {
+        (StatusCode::BAD_REQUEST, "No API entry point").into_response()
     }
 }
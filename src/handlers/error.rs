@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::Json;
+
+#[derive(Debug)]
+pub struct HandlerError {
+    status: StatusCode,
+    message: String,
+}
+
+pub type HandlerResult = Result<Response, HandlerError>;
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    ok: bool,
+    message: &'a str,
+}
+
+impl HandlerError {
+    pub fn new(status: StatusCode, message: &str) -> HandlerError {
+        HandlerError {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request<E>(message: &str, error: E) -> HandlerError
+    where
+        E: Display,
+    {
+        warn!("{}: {}", message, error);
+
+        HandlerError::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    pub fn server_error<E>(message: &str, error: E) -> HandlerError
+    where
+        E: Display,
+    {
+        warn!("{}: {}", message, error);
+
+        HandlerError::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            ok: false,
+            message: &self.message,
+        };
+
+        (self.status, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use axum::extract;
+use axum::http::Extensions;
+use axum::response::sse;
+use axum::response::sse::KeepAlive;
+use axum::response::sse::Sse;
+use axum::response::IntoResponse;
+use futures::stream;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+
+use database_monitor_api::Event;
+
+use crate::auth::visibility;
+use crate::shutdown::Shutdown;
+use crate::state::State;
+use crate::state::Visibility;
+
+use super::error::HandlerResult;
+
+pub struct EventsHandler {
+    state: State,
+    shutdown: Shutdown,
+}
+
+struct Subscription {
+    receiver: Receiver<Event>,
+    state: State,
+    shutdown: Shutdown,
+    visibility: Visibility,
+}
+
+impl Subscription {
+    fn is_visible(&self, event: &Event) -> bool {
+        match event.server_name() {
+            Some(server_name) => self.visibility.is_visible(server_name),
+            None => true,
+        }
+    }
+
+    async fn next(&mut self) -> Option<Event> {
+        loop {
+            let received = tokio::select! {
+                received = self.receiver.recv() => received,
+                _ = self.shutdown.requested() => return None,
+            };
+
+            match received {
+                Ok(event) => {
+                    if self.is_visible(&event) {
+                        return Some(event);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!("Event subscriber lagged behind by {} events", skipped);
+
+                    return Some(Event::refresh_finished(self.state.last_update()));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl EventsHandler {
+    pub fn new(state: State, shutdown: Shutdown) -> EventsHandler {
+        EventsHandler { state, shutdown }
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+    ) -> HandlerResult {
+        let subscription = Subscription {
+            receiver: handler.state.subscribe(),
+            state: handler.state.clone(),
+            shutdown: handler.shutdown.clone(),
+            visibility: visibility(&extensions),
+        };
+        let events = stream::unfold(subscription, |mut subscription| async move {
+            let event = subscription.next().await?;
+            let message = sse::Event::default().event(event.name()).json_data(&event);
+
+            Some((message, subscription))
+        });
+
+        Ok(Sse::new(events)
+            .keep_alive(KeepAlive::default())
+            .into_response())
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::body::Bytes;
+use axum::extract;
+use axum::extract::Query as QueryParams;
+use axum::http::header;
+use axum::http::Extensions;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use futures::stream;
+
+use database_monitor_api::DatabasesRequest;
+
+use crate::auth::visibility;
+use crate::export::ExportChunks;
+use crate::export::ExportFormat;
+use crate::search::Query;
+use crate::state::State;
+
+use super::error::HandlerError;
+use super::error::HandlerResult;
+use super::sort::DatabaseSort;
+use super::util::json_body;
+
+pub struct ExportHandler {
+    state: State,
+}
+
+impl ExportHandler {
+    pub fn new(state: State) -> ExportHandler {
+        ExportHandler { state }
+    }
+
+    fn export_format(params: &HashMap<String, String>) -> Result<ExportFormat, String> {
+        match params.get("format") {
+            Some(format) => format.parse(),
+            None => Ok(ExportFormat::default()),
+        }
+    }
+
+    fn sort(params: &HashMap<String, String>) -> Result<DatabaseSort, String> {
+        match params.get("sort") {
+            Some(sort) => sort.parse(),
+            None => Ok(DatabaseSort::default()),
+        }
+    }
+
+    fn content_type(format: ExportFormat) -> &'static str {
+        match format {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::JsonLines => "application/x-ndjson",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+        QueryParams(params): QueryParams<HashMap<String, String>>,
+        body: Bytes,
+    ) -> HandlerResult {
+        let format = Self::export_format(&params)
+            .map_err(|err| HandlerError::bad_request("Invalid export format", err))?;
+        let sort = Self::sort(&params)
+            .map_err(|err| HandlerError::bad_request("Invalid sort field", err))?;
+        let request: DatabasesRequest = json_body(&body)?;
+
+        if request.query().len() > 64 {
+            return Err(HandlerError::new(
+                StatusCode::BAD_REQUEST,
+                "Query string too large",
+            ));
+        }
+
+        let query: Query = request.query().clone().into();
+        let mut rows =
+            handler
+                .state
+                .query(&query, &visibility(&extensions), request.include_replicas());
+        rows.sort_by(|a, b| sort.compare(a, b));
+
+        let body = Body::from_stream(stream::iter(ExportChunks::new(format, rows)));
+        let disposition = format!("attachment; filename=\"databases.{}\"", format.extension());
+
+        Ok((
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, Self::content_type(format).to_string()),
+                (header::CONTENT_DISPOSITION, disposition),
+            ],
+            body,
+        )
+            .into_response())
+    }
+}
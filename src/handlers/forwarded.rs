@@ -0,0 +1,78 @@
+use std::net::IpAddr;
+
+use axum::http::uri::Authority;
+use axum::http::HeaderMap;
+
+use crate::config::BasePath;
+
+const FORWARDED_HOST: &str = "x-forwarded-host";
+const FORWARDED_PREFIX: &str = "x-forwarded-prefix";
+const FORWARDED_PROTO: &str = "x-forwarded-proto";
+const FORWARDED_HEADERS: &[&str] = &[FORWARDED_HOST, FORWARDED_PREFIX, FORWARDED_PROTO];
+
+#[derive(Debug, Clone, Default)]
+pub struct Forwarded {
+    host: Option<Authority>,
+    prefix: Option<BasePath>,
+    proto: Option<String>,
+}
+
+fn first_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    let value = headers.get(name)?.to_str().ok()?;
+    let first = value.split(',').next()?.trim();
+
+    if first.is_empty() {
+        None
+    } else {
+        Some(first.to_string())
+    }
+}
+
+impl Forwarded {
+    pub fn from_request(headers: &HeaderMap, remote: IpAddr, trusted: &[IpAddr]) -> Forwarded {
+        if trusted.contains(&remote) {
+            return Forwarded::from_headers(headers);
+        }
+
+        if FORWARDED_HEADERS.iter().any(|name| headers.contains_key(*name)) {
+            debug!("Ignoring forwarded headers from untrusted address {}", remote);
+        }
+
+        Forwarded::default()
+    }
+
+    fn from_headers(headers: &HeaderMap) -> Forwarded {
+        let host = first_value(headers, FORWARDED_HOST).and_then(|host| host.parse().ok());
+        let prefix = first_value(headers, FORWARDED_PREFIX).and_then(|prefix| {
+            prefix
+                .parse()
+                .map_err(|err| debug!("Ignoring {} header: {}", FORWARDED_PREFIX, err))
+                .ok()
+        });
+        let proto = first_value(headers, FORWARDED_PROTO)
+            .map(|proto| proto.to_ascii_lowercase())
+            .filter(|proto| proto == "http" || proto == "https");
+
+        Forwarded {
+            host,
+            prefix,
+            proto,
+        }
+    }
+
+    pub fn host(&self) -> &Option<Authority> {
+        &self.host
+    }
+
+    pub fn prefix(&self) -> &Option<BasePath> {
+        &self.prefix
+    }
+
+    pub fn proto(&self) -> &Option<String> {
+        &self.proto
+    }
+
+    pub fn base_path<'a>(&'a self, base_path: &'a BasePath) -> &'a BasePath {
+        self.prefix.as_ref().unwrap_or(base_path)
+    }
+}
@@ -0,0 +1,44 @@
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use axum::body::Body;
+use bytes::Bytes;
+use http_body::Frame;
+use http_body::SizeHint;
+
+use crate::shutdown::RequestGuard;
+
+pub struct GuardedBody {
+    body: Body,
+    _guard: RequestGuard,
+}
+
+impl GuardedBody {
+    pub fn new(body: Body, guard: RequestGuard) -> GuardedBody {
+        GuardedBody {
+            body,
+            _guard: guard,
+        }
+    }
+}
+
+impl http_body::Body for GuardedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        Pin::new(&mut self.get_mut().body).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
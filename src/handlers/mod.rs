@@ -1,10 +1,37 @@
-#[macro_use]
-mod util;
-
+mod assets;
+mod convert;
 mod databases;
+mod drain;
+mod drop_database;
 mod empty;
+mod error;
+mod events;
+mod export;
+mod forwarded;
+mod guarded_body;
+mod openapi;
+mod redirect;
+mod refresh;
+mod server;
+mod server_databases;
+mod servers;
+mod sort;
 mod status;
+mod util;
 
+pub use self::assets::AssetsHandler;
 pub use self::databases::DatabasesHandler;
+pub use self::drain::DrainHandler;
+pub use self::drop_database::DropDatabaseHandler;
 pub use self::empty::EmptyHandler;
+pub use self::error::HandlerError;
+pub use self::error::HandlerResult;
+pub use self::events::EventsHandler;
+pub use self::export::ExportHandler;
+pub use self::openapi::OpenApiHandler;
+pub use self::redirect::RedirectHandler;
+pub use self::refresh::RefreshHandler;
+pub use self::server::ServerHandler;
+pub use self::server_databases::ServerDatabasesHandler;
+pub use self::servers::ServersHandler;
 pub use self::status::StatusHandler;
@@ -0,0 +1,19 @@
+use axum::http::header;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+
+use database_monitor_api::OPENAPI_DOCUMENT;
+
+pub struct OpenApiHandler;
+
+impl OpenApiHandler {
+    pub async fn handle() -> Response {
+        (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/json")],
+            OPENAPI_DOCUMENT,
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,87 @@
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract;
+use axum::extract::ConnectInfo;
+use axum::http::header;
+use axum::http::uri::Authority;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::Uri;
+use axum::response::IntoResponse;
+
+use super::error::HandlerError;
+use super::error::HandlerResult;
+use super::forwarded::Forwarded;
+
+pub struct RedirectHandler {
+    port: u16,
+    host: Option<String>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl RedirectHandler {
+    pub fn new(port: u16, host: Option<String>, trusted_proxies: Vec<IpAddr>) -> RedirectHandler {
+        RedirectHandler {
+            port,
+            host,
+            trusted_proxies,
+        }
+    }
+
+    fn request_host(headers: &HeaderMap) -> Result<String, String> {
+        let host = headers
+            .get(header::HOST)
+            .ok_or("Missing Host header")?
+            .to_str()
+            .map_err(|err| format!("{}", err))?
+            .parse::<Authority>()
+            .map_err(|err| format!("{}", err))?;
+
+        Ok(host.host().to_string())
+    }
+
+    fn https_url(
+        &self,
+        forwarded: &Forwarded,
+        headers: &HeaderMap,
+        uri: &Uri,
+    ) -> Result<String, String> {
+        if forwarded.proto().as_deref() == Some("https") {
+            return Err("Request already uses HTTPS, proxy should forward it to HTTPS port".into());
+        }
+
+        let path = uri
+            .path_and_query()
+            .map_or("/", |path_and_query| path_and_query.as_str());
+
+        if let Some(ref host) = *forwarded.host() {
+            let prefix = forwarded.prefix().clone().unwrap_or_default();
+
+            return Ok(format!("https://{}{}", host, prefix.join(path)));
+        }
+
+        let host = match self.host {
+            Some(ref host) => host.clone(),
+            None => Self::request_host(headers)?,
+        };
+
+        Ok(format!("https://{}:{}{}", host, self.port, path))
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+        headers: HeaderMap,
+        uri: Uri,
+    ) -> HandlerResult {
+        let forwarded =
+            Forwarded::from_request(&headers, remote_addr.ip(), &handler.trusted_proxies);
+        let url = handler
+            .https_url(&forwarded, &headers, &uri)
+            .map_err(|err| HandlerError::bad_request("Fail to build HTTPS URL", err))?;
+
+        Ok((StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, url)]).into_response())
+    }
+}
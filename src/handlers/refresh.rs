@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use axum::extract;
+use axum::extract::Path;
+use axum::http::header;
+use axum::http::Extensions;
+use axum::http::HeaderValue;
+use axum::http::StatusCode;
+use tokio::sync::mpsc::UnboundedSender;
+
+use database_monitor_api::RefreshResponse;
+
+use crate::auth::permission;
+use crate::auth::visibility;
+use crate::auth::Permission;
+use crate::state::Visibility;
+use crate::worker::WorkerMessage;
+
+use super::error::HandlerError;
+use super::error::HandlerResult;
+use super::util::json_response;
+
+const REFRESH_LIMIT: u64 = 30;
+
+pub struct RefreshHandler {
+    servers: HashSet<String>,
+    sender: Option<UnboundedSender<WorkerMessage>>,
+    last_refresh: Mutex<HashMap<Option<String>, Instant>>,
+}
+
+impl RefreshHandler {
+    pub fn new(
+        servers: HashSet<String>,
+        sender: Option<UnboundedSender<WorkerMessage>>,
+    ) -> RefreshHandler {
+        RefreshHandler {
+            servers,
+            sender,
+            last_refresh: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn retry_after(&self, server_name: &Option<String>) -> Result<Option<u64>, String> {
+        let mut last_refresh = self.last_refresh.lock().map_err(|err| format!("{}", err))?;
+        let now = Instant::now();
+        let limit = Duration::from_secs(REFRESH_LIMIT);
+
+        if let Some(last) = last_refresh.get(server_name) {
+            let elapsed = now.duration_since(*last);
+
+            if elapsed < limit {
+                return Ok(Some((limit - elapsed).as_secs() + 1));
+            }
+        }
+
+        last_refresh.insert(server_name.clone(), now);
+
+        Ok(None)
+    }
+
+    fn messages(
+        &self,
+        server_name: &Option<String>,
+        visibility: &Visibility,
+    ) -> Vec<WorkerMessage> {
+        match (server_name, visibility) {
+            (Some(server_name), _) => vec![WorkerMessage::RefreshServer(server_name.clone())],
+            (None, Visibility::All) => vec![WorkerMessage::RefreshAll],
+            (None, visibility) => self
+                .servers
+                .iter()
+                .filter(|server_name| visibility.is_visible(server_name))
+                .map(|server_name| WorkerMessage::RefreshServer(server_name.clone()))
+                .collect(),
+        }
+    }
+
+    fn send(&self, messages: Vec<WorkerMessage>) -> Result<(), String> {
+        let sender = match self.sender {
+            Some(ref sender) => sender,
+            None => return Err("Database worker is not running".into()),
+        };
+
+        for message in messages {
+            sender.send(message).map_err(|err| format!("{}", err))?;
+        }
+
+        Ok(())
+    }
+
+    fn refresh(&self, extensions: &Extensions, server_name: Option<String>) -> HandlerResult {
+        let visibility = visibility(extensions);
+
+        if !permission(extensions).allows(Permission::Refresh) {
+            let response = RefreshResponse::err("Refresh is not permitted");
+
+            return json_response(StatusCode::FORBIDDEN, &response);
+        }
+
+        if let Some(ref server_name) = server_name {
+            if !self.servers.contains(server_name) || !visibility.is_visible(server_name) {
+                let response = RefreshResponse::err("Server not found");
+
+                return json_response(StatusCode::NOT_FOUND, &response);
+            }
+        }
+
+        let retry_after = self
+            .retry_after(&server_name)
+            .map_err(|err| HandlerError::server_error("Fail to check refresh rate", err))?;
+
+        if let Some(retry_after) = retry_after {
+            let response = RefreshResponse::too_many_requests(retry_after);
+            let mut response = json_response(StatusCode::TOO_MANY_REQUESTS, &response)?;
+
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+
+            return Ok(response);
+        }
+
+        match self.send(self.messages(&server_name, &visibility)) {
+            Ok(()) => json_response(StatusCode::ACCEPTED, &RefreshResponse::ok()),
+            Err(err) => {
+                warn!("Failed to request refresh: {}", err);
+
+                json_response(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    &RefreshResponse::err("Refresh is not available"),
+                )
+            }
+        }
+    }
+
+    pub async fn handle_all(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+    ) -> HandlerResult {
+        handler.refresh(&extensions, None)
+    }
+
+    pub async fn handle_server(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+        Path(server_name): Path<String>,
+    ) -> HandlerResult {
+        handler.refresh(&extensions, Some(server_name))
+    }
+}
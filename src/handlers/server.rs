@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::extract;
+use axum::extract::Path;
+use axum::http::Extensions;
+use axum::http::StatusCode;
+
+use database_monitor_api::ServerDetailsResponse;
+
+use crate::auth::visibility;
+use crate::state::State;
+
+use super::convert;
+use super::error::HandlerResult;
+use super::util::json_response;
+
+pub struct ServerHandler {
+    state: State,
+}
+
+impl ServerHandler {
+    pub fn new(state: State) -> ServerHandler {
+        ServerHandler { state }
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+        Path(server_name): Path<String>,
+    ) -> HandlerResult {
+        match handler.state.server(&server_name, &visibility(&extensions)) {
+            Some((server_info, n_databases, replicas)) => {
+                let server = convert::server_details(&server_info, n_databases, &replicas);
+                let response = ServerDetailsResponse::ok(server);
+
+                json_response(StatusCode::OK, &response)
+            }
+            None => {
+                let response = ServerDetailsResponse::err("Server not found");
+
+                json_response(StatusCode::NOT_FOUND, &response)
+            }
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use axum::extract;
+use axum::extract::Path;
+use axum::http::Extensions;
+use axum::http::StatusCode;
+
+use database_monitor_api::DatabasesResponse;
+
+use crate::auth::visibility;
+use crate::state::State;
+
+use super::convert;
+use super::error::HandlerResult;
+use super::util::json_response;
+
+pub struct ServerDatabasesHandler {
+    state: State,
+}
+
+impl ServerDatabasesHandler {
+    pub fn new(state: State) -> ServerDatabasesHandler {
+        ServerDatabasesHandler { state }
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+        Path(server_name): Path<String>,
+    ) -> HandlerResult {
+        match handler
+            .state
+            .server_databases(&server_name, &visibility(&extensions))
+        {
+            Some(mut databases) => {
+                databases.sort_by(|a, b| a.database_name().cmp(b.database_name()));
+
+                let databases = databases.iter().map(convert::database).collect();
+                let response = DatabasesResponse::ok(databases);
+
+                json_response(StatusCode::OK, &response)
+            }
+            None => {
+                let response = DatabasesResponse::err("Server not found");
+
+                json_response(StatusCode::NOT_FOUND, &response)
+            }
+        }
+    }
+}
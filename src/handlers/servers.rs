@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use axum::extract;
+use axum::http::Extensions;
+use axum::http::StatusCode;
+
+use database_monitor_api::ServersResponse;
+
+use crate::auth::permission;
+use crate::auth::visibility;
+use crate::auth::Permission;
+use crate::state::State;
+
+use super::convert;
+use super::error::HandlerResult;
+use super::util::json_response;
+
+pub struct ServersHandler {
+    state: State,
+    managed_servers: HashSet<String>,
+}
+
+impl ServersHandler {
+    pub fn new(state: State, managed_servers: HashSet<String>) -> ServersHandler {
+        ServersHandler {
+            state,
+            managed_servers,
+        }
+    }
+
+    pub async fn handle(
+        extract::State(handler): extract::State<Arc<Self>>,
+        extensions: Extensions,
+    ) -> HandlerResult {
+        let mut servers = handler.state.servers(&visibility(&extensions));
+        servers.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let admin = permission(&extensions).allows(Permission::Admin);
+        let servers = servers
+            .iter()
+            .map(|row| {
+                let management = admin && handler.managed_servers.contains(row.name());
+
+                convert::server(row, management)
+            })
+            .collect();
+        let response = ServersResponse::ok(servers);
+
+        json_response(StatusCode::OK, &response)
+    }
+}
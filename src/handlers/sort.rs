@@ -0,0 +1,75 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::state::DatabaseRow;
+
+use super::DatabasesHandler;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    Weight,
+    Name,
+    Server,
+    Age,
+    LastUpdate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseSort {
+    field: SortField,
+    reverse: bool,
+}
+
+impl DatabaseSort {
+    pub fn compare(&self, a: &DatabaseRow, b: &DatabaseRow) -> Ordering {
+        let ordering = match self.field {
+            SortField::Weight => DatabasesHandler::compare_databases(a, b),
+            SortField::Name => a.database_name().cmp(b.database_name()),
+            SortField::Server => a.server_name().cmp(b.server_name()),
+            SortField::Age => b.created_or_first_seen().cmp(&a.created_or_first_seen()),
+            SortField::LastUpdate => a.last_update().cmp(&b.last_update()),
+        };
+        let ordering = if self.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+
+        ordering.then_with(|| DatabasesHandler::compare_databases(a, b))
+    }
+}
+
+impl FromStr for DatabaseSort {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<DatabaseSort, String> {
+        let (reverse, name) = match value.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, value),
+        };
+        let field = match name {
+            "weight" => SortField::Weight,
+            "name" => SortField::Name,
+            "server" => SortField::Server,
+            "age" => SortField::Age,
+            "last_update" => SortField::LastUpdate,
+            _ => {
+                return Err(format!(
+                    "Unknown sort field {}, expected one of: {}",
+                    name, "weight, name, server, age, last_update"
+                ))
+            }
+        };
+
+        Ok(DatabaseSort { field, reverse })
+    }
+}
+
+impl Default for DatabaseSort {
+    fn default() -> DatabaseSort {
+        DatabaseSort {
+            field: SortField::Weight,
+            reverse: false,
+        }
+    }
+}
@@ -1,25 +1,39 @@
-macro_rules! or_bad_request {
-    ($param: expr, $message: tt) => {
-        match $param {
-            Ok(value) => value,
-            Err(err) => {
-                warn!("{}: {}", $message, err);
+use std::collections::HashMap;
 
-                return Ok(Response::with((status::BadRequest, $message)));
-            }
-        }
-    }
+use axum::http::header;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::error::HandlerError;
+use super::error::HandlerResult;
+
+pub fn query_flag(params: &HashMap<String, String>, name: &str) -> bool {
+    params
+        .get(name)
+        .is_some_and(|value| value == "true" || value == "1")
+}
+
+pub fn json_body<T>(body: &[u8]) -> Result<T, HandlerError>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_slice(body)
+        .map_err(|err| HandlerError::bad_request("Fail to decode request body as JSON", err))
 }
 
-macro_rules! or_server_error {
-    ($param: expr, $message: tt) => {
-        match $param {
-            Ok(value) => value,
-            Err(err) => {
-                warn!("{}: {}", $message, err);
+pub fn json_response<T>(status: StatusCode, value: &T) -> HandlerResult
+where
+    T: Serialize,
+{
+    let json_records = serde_json::to_string(value)
+        .map_err(|err| HandlerError::server_error("Fail to convert records to JSON", err))?;
 
-                return Ok(Response::with((status::InternalServerError, $message)));
-            }
-        }
-    }
+    Ok((
+        status,
+        [(header::CONTENT_TYPE, "application/json")],
+        json_records,
+    )
+        .into_response())
 }
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate log;
+
+#[macro_use]
+extern crate serde_derive;
+
+pub mod assets;
+pub mod auth;
+pub mod check;
+pub mod config;
+pub mod events;
+pub mod export;
+pub mod handlers;
+pub mod logger;
+pub mod management;
+pub mod once;
+pub mod pattern;
+pub mod retention;
+pub mod search;
+pub mod shutdown;
+pub mod state;
+pub mod worker;
@@ -5,24 +5,22 @@ use env_logger::Builder;
 use env_logger::Formatter;
 use log::Record;
 
-use time;
-
 fn format(f: &mut Formatter, record: &Record) -> IoResult<()> {
     let t = time::now();
 
     if let Ok(time_string) = time::strftime("%Y-%m-%d %H:%M:%S", &t) {
-        write!(
+        writeln!(
             f,
-            "{}: {} [{}] - {}\n",
+            "{}: {} [{}] - {}",
             time_string,
             record.level(),
             record.target(),
             record.args()
         )
     } else {
-        write!(
+        writeln!(
             f,
-            "????-??-?? ??:??:??: {} [{}] - {}\n",
+            "????-??-?? ??:??:??: {} [{}] - {}",
             record.level(),
             record.target(),
             record.args()
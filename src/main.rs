@@ -1,123 +1,452 @@
 #[macro_use]
 extern crate log;
 
-#[macro_use]
-extern crate serde_derive;
-
-extern crate argparse;
-extern crate env_logger;
-extern crate iron;
-extern crate mount;
-extern crate postgres;
-extern crate router;
-extern crate serde_json;
-extern crate staticfile;
-extern crate time;
-
-mod config;
-mod handlers;
-mod logger;
-mod search;
-mod state;
-mod worker;
-
-use config::Configuration;
-use handlers::DatabasesHandler;
-use handlers::EmptyHandler;
-use handlers::StatusHandler;
-use state::State;
-use worker::DatabaseWorker;
-use worker::MetadataWorker;
-
-use iron::Iron;
-use mount::Mount;
-use router::Router;
-use staticfile::Static;
-
-fn start_database_worker(config: &Configuration, state: State) -> Option<DatabaseWorker> {
-    info!("Starting database worker thread");
+use database_monitor::assets::Assets;
+use database_monitor::auth::AuthMiddleware;
+use database_monitor::check;
+use database_monitor::config::Arguments;
+use database_monitor::config::Configuration;
+use database_monitor::handlers::AssetsHandler;
+use database_monitor::handlers::DatabasesHandler;
+use database_monitor::handlers::DrainHandler;
+use database_monitor::handlers::DropDatabaseHandler;
+use database_monitor::handlers::EmptyHandler;
+use database_monitor::handlers::EventsHandler;
+use database_monitor::handlers::ExportHandler;
+use database_monitor::handlers::OpenApiHandler;
+use database_monitor::handlers::RedirectHandler;
+use database_monitor::handlers::RefreshHandler;
+use database_monitor::handlers::ServerDatabasesHandler;
+use database_monitor::handlers::ServerHandler;
+use database_monitor::handlers::ServersHandler;
+use database_monitor::handlers::StatusHandler;
+use database_monitor::logger;
+use database_monitor::management::Manager;
+use database_monitor::once;
+use database_monitor::retention::RetentionPolicy;
+use database_monitor::shutdown::Shutdown;
+use database_monitor::state::State;
+use database_monitor::worker::Connector;
+use database_monitor::worker::DatabaseWorker;
+use database_monitor::worker::DefaultConnector;
+use database_monitor::worker::MetadataWorker;
+
+use std::collections::HashSet;
+use std::env;
+use std::future::Future;
+use std::io::Result as IoResult;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::middleware;
+use axum::routing::delete;
+use axum::routing::get;
+use axum::routing::post;
+use axum::Router;
+use axum_server::tls_openssl::OpenSSLAcceptor;
+use axum_server::tls_openssl::OpenSSLConfig;
+use axum_server::Handle;
+use tokio::signal::unix::signal;
+use tokio::signal::unix::SignalKind;
+use tokio::task::JoinHandle;
+
+fn start_database_worker(
+    config: &Configuration,
+    state: State,
+    connector: Arc<dyn Connector>,
+    metadata_worker: &Option<MetadataWorker>,
+) -> DatabaseWorker {
+    info!("Starting database worker");
 
     let servers = config.servers().clone();
     let interval = config.interval();
+    let probe_timeout = config.probe_timeout();
+    let metadata_sender = metadata_worker.as_ref().map(|worker| worker.sender());
 
-    match DatabaseWorker::spawn(servers, interval, state) {
-        Ok(worker) => Some(worker),
-        Err(err) => {
-            error!("Failed to spawn database worker thread: {}", err);
-
-            None
-        }
-    }
+    DatabaseWorker::spawn(
+        servers,
+        interval,
+        probe_timeout,
+        state,
+        connector,
+        metadata_sender,
+    )
 }
 
-fn start_metadata_worker(config: &Configuration, state: State) -> Option<MetadataWorker> {
+fn start_metadata_worker(
+    config: &Configuration,
+    state: State,
+    connector: Arc<dyn Connector>,
+) -> Option<MetadataWorker> {
     let interval = config.interval();
-    let metadata_config = match config.metadata() {
-        &Some(ref metadata_config) => metadata_config.clone(),
-        &None => return None,
+    let metadata_config = config.metadata().clone()?;
+
+    info!("Starting metadata worker");
+
+    Some(MetadataWorker::spawn(
+        metadata_config,
+        interval,
+        state,
+        connector,
+    ))
+}
+
+fn initialize_server(
+    config: &Configuration,
+    state: State,
+    database_worker: &DatabaseWorker,
+    manager: Option<Manager>,
+    shutdown: &Shutdown,
+    trusted_proxies: Vec<IpAddr>,
+) -> Router {
+    let server_names: HashSet<String> = config
+        .servers()
+        .iter()
+        .map(|server| server.host().clone())
+        .collect();
+    let refresh = Arc::new(RefreshHandler::new(
+        server_names,
+        Some(database_worker.sender()),
+    ));
+    let managed_servers = match manager {
+        Some(ref manager) => manager.managed_servers(),
+        None => HashSet::new(),
     };
+    let status = Arc::new(StatusHandler::new(state.clone()));
+    let databases = Arc::new(DatabasesHandler::new(state.clone()));
+    let api = Router::new()
+        .route(
+            "/status",
+            get(StatusHandler::handle)
+                .post(StatusHandler::handle)
+                .with_state(status),
+        )
+        .route(
+            "/databases",
+            get(DatabasesHandler::handle_get)
+                .post(DatabasesHandler::handle_post)
+                .with_state(databases),
+        )
+        .route(
+            "/databases/export",
+            post(ExportHandler::handle).with_state(Arc::new(ExportHandler::new(state.clone()))),
+        )
+        .route(
+            "/events",
+            get(EventsHandler::handle).with_state(Arc::new(EventsHandler::new(
+                state.clone(),
+                shutdown.clone(),
+            ))),
+        )
+        .route(
+            "/servers",
+            get(ServersHandler::handle).with_state(Arc::new(ServersHandler::new(
+                state.clone(),
+                managed_servers,
+            ))),
+        )
+        .route(
+            "/servers/{name}",
+            get(ServerHandler::handle).with_state(Arc::new(ServerHandler::new(state.clone()))),
+        )
+        .route(
+            "/servers/{name}/databases",
+            get(ServerDatabasesHandler::handle)
+                .with_state(Arc::new(ServerDatabasesHandler::new(state.clone()))),
+        )
+        .route(
+            "/servers/{name}/databases/{database}",
+            delete(DropDatabaseHandler::handle)
+                .with_state(Arc::new(DropDatabaseHandler::new(state, manager))),
+        )
+        .route(
+            "/servers/refresh",
+            post(RefreshHandler::handle_all).with_state(refresh.clone()),
+        )
+        .route(
+            "/servers/{name}/refresh",
+            post(RefreshHandler::handle_server).with_state(refresh),
+        )
+        .route("/openapi.json", get(OpenApiHandler::handle))
+        .route("/", get(EmptyHandler::handle).post(EmptyHandler::handle));
 
-    info!("Starting metadata worker thread");
+    let base_path = config.base_path();
+    let assets = Arc::new(AssetsHandler::new(
+        Assets::new(config.assets_dir().as_ref().map(PathBuf::from)),
+        base_path.clone(),
+        trusted_proxies,
+    ));
+    let index = get(AssetsHandler::handle_index).with_state(assets.clone());
+    let router = Router::new()
+        .nest(&base_path.join("/api/v1"), api)
+        .route(
+            &base_path.join("/public/{*path}"),
+            get(AssetsHandler::handle_public).with_state(assets.clone()),
+        )
+        .route(
+            &base_path.join("/servers"),
+            get(AssetsHandler::handle_servers).with_state(assets),
+        )
+        .route(&base_path.join("/"), index.clone());
 
-    match MetadataWorker::spawn(metadata_config, interval, state) {
-        Ok(worker) => Some(worker),
-        Err(err) => {
-            error!("Failed to spawn metadata worker thread: {}", err);
+    if base_path.is_root() {
+        router
+    } else {
+        router.route(base_path.as_str(), index)
+    }
+}
+
+const SHUTDOWN_TIMEOUT: u64 = 10;
+
+fn bind(address: &str, port: u16) -> IoResult<TcpListener> {
+    let listener = TcpListener::bind((address, port))?;
+    listener.set_nonblocking(true)?;
+
+    Ok(listener)
+}
+
+fn start_server(
+    config: &Configuration,
+    router: Router,
+    handle: Handle,
+) -> Result<JoinHandle<IoResult<()>>, String> {
+    let listener = bind(config.address(), config.port()).map_err(|err| format!("{}", err))?;
+    let service = router.into_make_service_with_connect_info::<SocketAddr>();
+
+    match config.tls() {
+        Some(tls_config) => {
+            let ssl = OpenSSLConfig::from_pem_file(tls_config.certificate(), tls_config.key())
+                .map_err(|err| format!("Failed to load TLS certificate or key: {}", err))?;
+
+            info!("Binding to https://{}:{}", config.address(), config.port());
 
-            None
+            let server = axum_server::from_tcp(listener)
+                .acceptor(OpenSSLAcceptor::new(ssl))
+                .handle(handle);
+
+            Ok(tokio::spawn(server.serve(service)))
+        }
+        None => {
+            info!("Binding to http://{}:{}", config.address(), config.port());
+
+            let server = axum_server::from_tcp(listener).handle(handle);
+
+            Ok(tokio::spawn(server.serve(service)))
         }
     }
 }
 
-fn initialize_server(state: State) -> Mount {
-    let mut router = Router::new();
-    let mut mount = Mount::new();
+fn start_redirect_server(
+    config: &Configuration,
+    handle: Handle,
+    trusted_proxies: Vec<IpAddr>,
+) -> Option<JoinHandle<IoResult<()>>> {
+    let tls_config = config.tls().as_ref()?;
+    let redirect_port = tls_config.redirect_port()?;
 
-    router.post("/status", StatusHandler::new(state.clone()), "status");
-    router.post(
-        "/databases",
-        DatabasesHandler::new(state.clone()),
-        "databases",
+    info!(
+        "Redirecting http://{}:{} to HTTPS",
+        config.address(),
+        redirect_port
     );
-    router.post("/", EmptyHandler::new(), "empty");
 
-    mount.mount("/public", Static::new("public/"));
-    mount.mount("/api/v1", router);
-    mount.mount("/", Static::new("template/index.html"));
+    let listener = match bind(config.address(), redirect_port) {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!("Can not start redirect server: {}", err);
 
-    mount
+            return None;
+        }
+    };
+    let redirect = RedirectHandler::new(
+        config.port(),
+        tls_config.redirect_host().clone(),
+        trusted_proxies,
+    );
+    let router = Router::new()
+        .fallback(RedirectHandler::handle)
+        .with_state(Arc::new(redirect));
+    let service = router.into_make_service_with_connect_info::<SocketAddr>();
+    let server = axum_server::from_tcp(listener).handle(handle);
+
+    Some(tokio::spawn(server.serve(service)))
 }
 
-fn main() {
+fn termination_signal() -> IoResult<impl Future<Output = ()>> {
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+
+    Ok(async move {
+        tokio::select! {
+            _ = terminate.recv() => {}
+            _ = interrupt.recv() => {}
+        }
+    })
+}
+
+async fn join_server(server: JoinHandle<IoResult<()>>, timeout: Duration) -> bool {
+    match tokio::time::timeout(timeout, server).await {
+        Ok(Ok(Ok(()))) => true,
+        Ok(Ok(Err(err))) => {
+            warn!("Server failed: {}", err);
+
+            false
+        }
+        Ok(Err(err)) => {
+            warn!("Server task failed: {}", err);
+
+            false
+        }
+        Err(_) => {
+            warn!("Server did not stop after {:?}", timeout);
+
+            false
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
     logger::init();
 
     info!("Reading configuration");
 
-    let config = match Configuration::from_args() {
+    let arguments = Arguments::from_args();
+
+    if arguments.check_config() {
+        process::exit(check::run(&arguments, env::vars()).await);
+    }
+
+    let config = match Configuration::load(&arguments, env::vars()) {
         Ok(config) => config,
         Err(err) => {
             error!("Failed to read configuration: {}", err);
 
-            return;
+            process::exit(1);
+        }
+    };
+
+    if let Some(once_options) = config.once() {
+        process::exit(once::run(&config, once_options).await);
+    }
+
+    let trusted_proxies = match config.trusted_proxies() {
+        Ok(trusted_proxies) => trusted_proxies,
+        Err(err) => {
+            error!("Failed to read trusted proxies: {}", err);
+
+            process::exit(1);
+        }
+    };
+    let manager = match Manager::new(&config) {
+        Ok(manager) => manager,
+        Err(err) => {
+            error!("Failed to initialize management mode: {}", err);
+
+            process::exit(1);
+        }
+    };
+    let state = match State::open(RetentionPolicy::new(config.retention()), config.state_file()) {
+        Ok(state) => state,
+        Err(err) => {
+            error!("Failed to load state file: {}", err);
+
+            process::exit(1);
+        }
+    };
+    let connector: Arc<dyn Connector> = Arc::new(DefaultConnector);
+    let metadata_worker = start_metadata_worker(&config, state.clone(), connector.clone());
+    let database_worker =
+        start_database_worker(&config, state.clone(), connector, &metadata_worker);
+    let shutdown = Shutdown::default();
+    let mut router = initialize_server(
+        &config,
+        state,
+        &database_worker,
+        manager,
+        &shutdown,
+        trusted_proxies.clone(),
+    );
+
+    if let Some(auth_config) = config.auth() {
+        match AuthMiddleware::new(auth_config, &trusted_proxies) {
+            Ok(auth) => {
+                router = router.layer(middleware::from_fn_with_state(
+                    Arc::new(auth),
+                    AuthMiddleware::handle,
+                ));
+            }
+            Err(err) => {
+                error!("Failed to initialize authentication: {}", err);
+
+                process::exit(1);
+            }
+        }
+    }
+
+    router = router.layer(middleware::from_fn_with_state(
+        Arc::new(DrainHandler::new(shutdown.clone())),
+        DrainHandler::handle,
+    ));
+
+    let signal = match termination_signal() {
+        Ok(signal) => signal,
+        Err(err) => {
+            error!("Failed to install signal handler: {}", err);
+
+            process::exit(1);
         }
     };
+    let handle = Handle::new();
+    let redirect_handle = Handle::new();
+    let redirect_server =
+        start_redirect_server(&config, redirect_handle.clone(), trusted_proxies);
+    let server = match start_server(&config, router, handle.clone()) {
+        Ok(server) => server,
+        Err(err) => {
+            error!("Can not start server: {}", err);
 
-    let state = State::default();
-    let database_worker = start_database_worker(&config, state.clone());
-    let metadata_worker = start_metadata_worker(&config, state.clone());
-    let mount = initialize_server(state);
+            process::exit(1);
+        }
+    };
+
+    signal.await;
+
+    info!("Shutting down");
+
+    let timeout = Duration::from_secs(SHUTDOWN_TIMEOUT);
+    let mut clean = true;
+
+    shutdown.request();
+    handle.graceful_shutdown(Some(timeout));
+    redirect_handle.graceful_shutdown(Some(timeout));
 
-    info!("Binding to {}:{}", config.address(), config.port());
+    clean &= join_server(server, timeout).await;
 
-    if let Err(err) = Iron::new(mount).http((config.address().as_ref(), config.port())) {
-        error!("Can not start server: {}", err);
+    if let Some(redirect_server) = redirect_server {
+        clean &= join_server(redirect_server, timeout).await;
     }
 
-    if let Some(database_worker) = database_worker {
-        database_worker.join();
+    clean &= shutdown.wait_idle(timeout).await;
+
+    database_worker.shutdown();
+
+    if let Some(ref metadata_worker) = metadata_worker {
+        metadata_worker.shutdown();
     }
 
+    clean &= database_worker.join_timeout(timeout).await;
+
     if let Some(metadata_worker) = metadata_worker {
-        metadata_worker.join();
+        clean &= metadata_worker.join_timeout(timeout).await;
     }
+
+    info!("Server stopped");
+
+    process::exit(if clean { 0 } else { 1 });
 }
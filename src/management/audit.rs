@@ -0,0 +1,90 @@
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Result as IoResult;
+use std::io::Write;
+use std::sync::Mutex;
+
+use super::ManagementError;
+use super::ManagementResult;
+
+#[derive(Serialize, Debug)]
+pub struct AuditEntry<'a> {
+    time: i64,
+    user: Option<&'a str>,
+    address: &'a str,
+    action: &'a str,
+    server: &'a str,
+    database: &'a str,
+    backends: Option<i64>,
+    result: &'a str,
+}
+
+#[derive(Debug)]
+pub struct AuditLog {
+    file: Option<Mutex<File>>,
+}
+
+impl<'a> AuditEntry<'a> {
+    pub fn new(
+        action: &'a str,
+        server: &'a str,
+        database: &'a str,
+        result: &'a str,
+    ) -> AuditEntry<'a> {
+        AuditEntry {
+            time: time::get_time().sec,
+            user: None,
+            address: "",
+            action,
+            server,
+            database,
+            backends: None,
+            result,
+        }
+    }
+
+    pub fn with_user(mut self, user: Option<&'a str>, address: &'a str) -> AuditEntry<'a> {
+        self.user = user;
+        self.address = address;
+        self
+    }
+
+    pub fn with_backends(mut self, backends: Option<i64>) -> AuditEntry<'a> {
+        self.backends = backends;
+        self
+    }
+}
+
+impl AuditLog {
+    pub fn open(path: &Option<String>) -> IoResult<AuditLog> {
+        let file = match path {
+            Some(path) => {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+                Some(Mutex::new(file))
+            }
+            None => None,
+        };
+
+        Ok(AuditLog { file })
+    }
+
+    pub fn record(&self, entry: &AuditEntry) -> ManagementResult<()> {
+        let line = serde_json::to_string(entry).map_err(|err| ManagementError::AuditError {
+            message: format!("{}", err),
+        })?;
+
+        info!("Audit: {}", line);
+
+        if let Some(ref file) = self.file {
+            let mut file = file.lock().map_err(|err| ManagementError::AuditError {
+                message: format!("{}", err),
+            })?;
+
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+}
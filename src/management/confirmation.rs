@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use crate::auth::constant_time_eq;
+
+pub const CONFIRMATION_TIMEOUT: u64 = 300;
+
+const TOKEN_BYTES: usize = 16;
+
+#[derive(Debug)]
+pub struct Confirmations {
+    pending: Mutex<HashMap<(String, String), (String, Instant)>>,
+}
+
+impl Confirmations {
+    pub fn new() -> Confirmations {
+        Confirmations {
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn issue(&self, server_name: &str, database_name: &str) -> String {
+        let mut bytes = [0u8; TOKEN_BYTES];
+
+        OsRng.fill_bytes(&mut bytes);
+
+        let token: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        let mut pending = self.pending
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        let timeout = Duration::from_secs(CONFIRMATION_TIMEOUT);
+
+        pending.retain(|_, &mut (_, issued)| now.duration_since(issued) < timeout);
+        pending.insert(
+            (server_name.into(), database_name.into()),
+            (token.clone(), now),
+        );
+
+        token
+    }
+
+    pub fn take(&self, server_name: &str, database_name: &str, token: &str) -> bool {
+        let mut pending = self.pending
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let key = (server_name.to_string(), database_name.to_string());
+        let timeout = Duration::from_secs(CONFIRMATION_TIMEOUT);
+        let valid = match pending.get(&key) {
+            Some(&(ref expected, issued)) => {
+                constant_time_eq(expected.as_bytes(), token.as_bytes())
+                    && issued.elapsed() < timeout
+            }
+            None => false,
+        };
+
+        if valid {
+            pending.remove(&key);
+        }
+
+        valid
+    }
+}
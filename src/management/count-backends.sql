@@ -0,0 +1,5 @@
+SELECT
+    (SELECT count(*) FROM pg_stat_activity AS a WHERE a.datname = d.datname)
+FROM pg_database AS d
+WHERE
+    d.datname = $1
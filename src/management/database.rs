@@ -0,0 +1,98 @@
+use tokio_postgres::Client;
+
+use crate::config::ManagementRole;
+use crate::config::ServerConnInfo;
+use crate::worker::connect_postgres;
+
+use super::ManagementResult;
+
+pub async fn connect(
+    connection_info: &ServerConnInfo,
+    role: &ManagementRole,
+) -> ManagementResult<Client> {
+    let client = connect_postgres(
+        connection_info.host(),
+        connection_info.port(),
+        role.role(),
+        role.password(),
+        "postgres",
+    )
+    .await?;
+
+    Ok(client)
+}
+
+pub async fn count_backends(conn: &Client, database_name: &str) -> ManagementResult<Option<i64>> {
+    let rows = conn
+        .query(include_str!("count-backends.sql"), &[&database_name])
+        .await?;
+
+    Ok(rows.first().map(|row| row.get(0)))
+}
+
+const FORCE_DROP_VERSION: i32 = 130000;
+
+async fn server_version(conn: &Client) -> ManagementResult<i32> {
+    let row = conn.query_one("SELECT current_setting('server_version_num')::int", &[]).await?;
+
+    Ok(row.get(0))
+}
+
+async fn terminate_backends(conn: &Client, database_name: &str) -> ManagementResult<i64> {
+    let rows = conn
+        .query(include_str!("terminate-backends.sql"), &[&database_name])
+        .await?;
+
+    Ok(rows.first().map(|row| row.get(0)).unwrap_or(0))
+}
+
+async fn allow_connections(
+    conn: &Client,
+    database_name: &str,
+    allow: bool,
+) -> ManagementResult<()> {
+    conn.batch_execute(&format!(
+        "ALTER DATABASE {} ALLOW_CONNECTIONS {}",
+        quote_identifier(database_name),
+        allow
+    ))
+    .await?;
+
+    Ok(())
+}
+
+pub async fn force_drop_database(conn: &Client, database_name: &str) -> ManagementResult<i64> {
+    let name = quote_identifier(database_name);
+
+    if server_version(conn).await? >= FORCE_DROP_VERSION {
+        let backends = count_backends(conn, database_name).await?.unwrap_or(0);
+
+        conn.batch_execute(&format!("DROP DATABASE {} WITH (FORCE)", name))
+            .await?;
+
+        return Ok(backends);
+    }
+
+    allow_connections(conn, database_name, false).await?;
+
+    let result = match terminate_backends(conn, database_name).await {
+        Ok(backends) => conn
+            .batch_execute(&format!("DROP DATABASE {}", name))
+            .await
+            .map(|_| backends)
+            .map_err(From::from),
+        Err(err) => Err(err),
+    };
+
+    if result.is_err() {
+        if let Err(err) = allow_connections(conn, database_name, true).await {
+            warn!("Failed to allow connections to {}: {}", database_name, err);
+        }
+    }
+
+    result
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -0,0 +1,73 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FtmResult;
+use std::io::Error as IoError;
+
+use tokio_postgres::Error as PgError;
+
+pub type ManagementResult<T> = Result<T, ManagementError>;
+
+#[derive(Debug)]
+pub enum ManagementError {
+    ServerNotManaged { server: String },
+    ProtectedDatabase { database: String },
+    DatabaseNotFound { server: String, database: String },
+    ConfirmationRequired,
+    DatabaseError { message: String },
+    AuditError { message: String },
+}
+
+impl Display for ManagementError {
+    fn fmt(&self, f: &mut Formatter) -> FtmResult {
+        match *self {
+            ManagementError::ServerNotManaged { ref server } => {
+                write!(f, "Management is not enabled for server {}", server)
+            }
+            ManagementError::ProtectedDatabase { ref database } => {
+                write!(f, "Database {} is protected", database)
+            }
+            ManagementError::DatabaseNotFound {
+                ref server,
+                ref database,
+            } => write!(f, "Database {} not found on server {}", database, server),
+            ManagementError::ConfirmationRequired => write!(
+                f,
+                "Valid confirmation token required, request dry run to get one"
+            ),
+            ManagementError::DatabaseError { ref message } => write!(f, "{}", message),
+            ManagementError::AuditError { ref message } => {
+                write!(f, "Failed to write audit log: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for ManagementError {
+    fn description(&self) -> &str {
+        match *self {
+            ManagementError::ServerNotManaged { .. } => "Server not managed",
+            ManagementError::ProtectedDatabase { .. } => "Protected database",
+            ManagementError::DatabaseNotFound { .. } => "Database not found",
+            ManagementError::ConfirmationRequired => "Confirmation required",
+            ManagementError::DatabaseError { .. } => "Database error",
+            ManagementError::AuditError { .. } => "Audit error",
+        }
+    }
+}
+
+impl From<PgError> for ManagementError {
+    fn from(error: PgError) -> ManagementError {
+        ManagementError::DatabaseError {
+            message: format!("{}", error),
+        }
+    }
+}
+
+impl From<IoError> for ManagementError {
+    fn from(error: IoError) -> ManagementError {
+        ManagementError::AuditError {
+            message: format!("{}", error),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+
+use crate::config::Configuration;
+use crate::config::ManagementRole;
+use crate::config::ServerConnInfo;
+use crate::config::ServerKind;
+
+use super::database;
+use super::AuditEntry;
+use super::AuditLog;
+use super::Confirmations;
+use super::ManagementError;
+use super::ManagementResult;
+use super::ProtectedNames;
+
+#[derive(Debug)]
+pub struct DropPlan {
+    backends: i64,
+    confirm: String,
+}
+
+#[derive(Debug)]
+pub struct Manager {
+    servers: HashMap<String, (ServerConnInfo, ManagementRole)>,
+    protected: ProtectedNames,
+    audit: AuditLog,
+    confirmations: Confirmations,
+}
+
+impl DropPlan {
+    pub fn backends(&self) -> i64 {
+        self.backends
+    }
+
+    pub fn confirm(&self) -> &String {
+        &self.confirm
+    }
+}
+
+impl Manager {
+    pub fn new(config: &Configuration) -> IoResult<Option<Manager>> {
+        let management_config = match config.management() {
+            Some(management_config) => management_config,
+            None => return Ok(None),
+        };
+
+        if config.auth().is_none() {
+            return Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "Management mode requires auth section",
+            ));
+        }
+
+        let servers = config
+            .servers()
+            .iter()
+            .filter(|server| {
+                let supported = server.kind() == ServerKind::Postgres;
+
+                if !supported && server.management().is_some() {
+                    warn!(
+                        "Management mode is not supported for {:?} server {}",
+                        server.kind(),
+                        server.host()
+                    );
+                }
+
+                supported
+            })
+            .filter_map(|server| {
+                server
+                    .management()
+                    .as_ref()
+                    .map(|role| (server.host().clone(), (server.clone(), role.clone())))
+            })
+            .collect();
+
+        Ok(Some(Manager {
+            servers,
+            protected: ProtectedNames::new(management_config.protected()),
+            audit: AuditLog::open(management_config.audit_log())?,
+            confirmations: Confirmations::new(),
+        }))
+    }
+
+    pub fn managed_servers(&self) -> HashSet<String> {
+        self.servers.keys().cloned().collect()
+    }
+
+    pub async fn plan(
+        &self,
+        server_name: &str,
+        database_name: &str,
+        user: Option<&str>,
+        address: &str,
+    ) -> ManagementResult<DropPlan> {
+        let result = self.plan_drop(server_name, database_name).await;
+        let (backends, message) = match result {
+            Ok(ref plan) => (Some(plan.backends), "ok".to_string()),
+            Err(ref err) => (None, format!("{}", err)),
+        };
+
+        let entry = AuditEntry::new("dry_run", server_name, database_name, &message)
+            .with_user(user, address)
+            .with_backends(backends);
+
+        self.audit.record(&entry)?;
+
+        result
+    }
+
+    pub async fn drop_database(
+        &self,
+        server_name: &str,
+        database_name: &str,
+        confirm: &str,
+        user: Option<&str>,
+        address: &str,
+    ) -> ManagementResult<i64> {
+        let result = self
+            .execute_drop(server_name, database_name, confirm, user, address)
+            .await;
+        let (backends, message) = match result {
+            Ok(backends) => (Some(backends), "ok".to_string()),
+            Err(ref err) => (None, format!("{}", err)),
+        };
+
+        let entry = AuditEntry::new("drop", server_name, database_name, &message)
+            .with_user(user, address)
+            .with_backends(backends);
+
+        if let Err(err) = self.audit.record(&entry) {
+            error!("Failed to record drop of {} on {}: {}", database_name, server_name, err);
+        }
+
+        result
+    }
+
+    fn check(
+        &self,
+        server_name: &str,
+        database_name: &str,
+    ) -> ManagementResult<&(ServerConnInfo, ManagementRole)> {
+        let server = self.servers
+            .get(server_name)
+            .ok_or_else(|| ManagementError::ServerNotManaged {
+                server: server_name.into(),
+            })?;
+
+        if self.protected.is_protected(database_name) {
+            return Err(ManagementError::ProtectedDatabase {
+                database: database_name.into(),
+            });
+        }
+
+        Ok(server)
+    }
+
+    async fn plan_drop(
+        &self,
+        server_name: &str,
+        database_name: &str,
+    ) -> ManagementResult<DropPlan> {
+        let (connection_info, role) = self.check(server_name, database_name)?;
+        let conn = database::connect(connection_info, role).await?;
+        let backends = database::count_backends(&conn, database_name)
+            .await?
+            .ok_or_else(|| ManagementError::DatabaseNotFound {
+                server: server_name.into(),
+                database: database_name.into(),
+            })?;
+        let confirm = self.confirmations.issue(server_name, database_name);
+
+        Ok(DropPlan { backends, confirm })
+    }
+
+    async fn execute_drop(
+        &self,
+        server_name: &str,
+        database_name: &str,
+        confirm: &str,
+        user: Option<&str>,
+        address: &str,
+    ) -> ManagementResult<i64> {
+        let (connection_info, role) = self.check(server_name, database_name)?;
+
+        if !self.confirmations.take(server_name, database_name, confirm) {
+            return Err(ManagementError::ConfirmationRequired);
+        }
+
+        let entry = AuditEntry::new("drop", server_name, database_name, "started")
+            .with_user(user, address);
+
+        self.audit.record(&entry)?;
+
+        let conn = database::connect(connection_info, role).await?;
+
+        if database::count_backends(&conn, database_name)
+            .await?
+            .is_none()
+        {
+            return Err(ManagementError::DatabaseNotFound {
+                server: server_name.into(),
+                database: database_name.into(),
+            });
+        }
+
+        let backends = database::force_drop_database(&conn, database_name).await?;
+
+        Ok(backends)
+    }
+}
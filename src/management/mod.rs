@@ -0,0 +1,17 @@
+mod audit;
+mod confirmation;
+mod database;
+mod error;
+mod manager;
+mod protected;
+
+use self::audit::AuditEntry;
+use self::audit::AuditLog;
+use self::confirmation::Confirmations;
+use self::error::ManagementResult;
+use self::protected::ProtectedNames;
+
+pub use self::confirmation::CONFIRMATION_TIMEOUT;
+pub use self::error::ManagementError;
+pub use self::manager::DropPlan;
+pub use self::manager::Manager;
@@ -0,0 +1,26 @@
+use crate::pattern;
+
+const BUILTIN_PROTECTED: &[&str] = &["postgres", "template0", "template1"];
+
+#[derive(Debug, Clone)]
+pub struct ProtectedNames {
+    patterns: Vec<String>,
+}
+
+impl ProtectedNames {
+    pub fn new(patterns: &[String]) -> ProtectedNames {
+        let patterns = BUILTIN_PROTECTED
+            .iter()
+            .map(|&name| name.to_string())
+            .chain(patterns.iter().cloned())
+            .collect();
+
+        ProtectedNames { patterns }
+    }
+
+    pub fn is_protected(&self, database_name: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern::matches(pattern, database_name))
+    }
+}
@@ -0,0 +1,6 @@
+SELECT
+    count(pg_terminate_backend(a.pid))
+FROM pg_stat_activity AS a
+WHERE
+    a.datname = $1 AND
+    a.pid <> pg_backend_pid()
@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::stdout;
+use std::io::BufWriter;
+use std::io::Result as IoResult;
+use std::io::Write;
+use std::time::Duration;
+
+use tokio::time;
+
+use crate::config::Configuration;
+use crate::config::OnceOptions;
+use crate::export::write_rows;
+use crate::retention::RetentionPolicy;
+use crate::state::State;
+use crate::worker::update_metadata;
+use crate::worker::Connector;
+use crate::worker::DefaultConnector;
+
+async fn probe_servers(connector: &dyn Connector, config: &Configuration, state: &State) -> usize {
+    let mut n_failed = 0;
+    let timeout = Duration::from_secs(config.probe_timeout());
+
+    for connection_info in config.servers() {
+        debug!("Updating server {}", connection_info.host());
+
+        let probe = connector.probe_server(connection_info, timeout);
+        let result = match time::timeout(timeout, probe).await {
+            Ok(result) => result.map_err(|err| err.to_string()),
+            Err(_) => Err(format!("Probe timed out after {} seconds", timeout.as_secs())),
+        };
+
+        match result {
+            Ok((details, dbs)) => state.update_server(
+                connection_info.host(),
+                connection_info.description(),
+                details,
+                dbs,
+            ),
+            Err(err) => {
+                error!(
+                    "Failed to update server {}: {}",
+                    connection_info.host(),
+                    err
+                );
+
+                n_failed += 1;
+            }
+        }
+    }
+
+    n_failed
+}
+
+async fn probe_metadata(connector: &dyn Connector, config: &Configuration, state: &State) -> usize {
+    let mut ignored_databases = HashSet::default();
+
+    match config.metadata() {
+        Some(metadata_config) => match update_metadata(
+            connector,
+            metadata_config,
+            state,
+            &mut ignored_databases,
+        )
+        .await
+        {
+            Ok(()) => 0,
+            Err(err) => {
+                error!("Failed to update metadata: {}", err);
+
+                1
+            }
+        },
+        None => 0,
+    }
+}
+
+fn write_output(options: &OnceOptions, state: &State) -> IoResult<()> {
+    let mut rows = state.databases();
+    rows.sort_by(|a, b| {
+        a.server_name()
+            .cmp(b.server_name())
+            .then_with(|| a.database_name().cmp(b.database_name()))
+    });
+
+    match options.output() {
+        Some(path) => {
+            let mut writer = BufWriter::new(File::create(path)?);
+
+            write_rows(options.format(), &rows, &mut writer)?;
+            writer.flush()
+        }
+        None => {
+            let stdout = stdout();
+            let mut writer = stdout.lock();
+
+            write_rows(options.format(), &rows, &mut writer)?;
+            writer.flush()
+        }
+    }
+}
+
+pub async fn run(config: &Configuration, options: &OnceOptions) -> i32 {
+    let state = match State::open(RetentionPolicy::new(config.retention()), config.state_file()) {
+        Ok(state) => state,
+        Err(err) => {
+            error!("Failed to load state file: {}", err);
+
+            return 1;
+        }
+    };
+    let connector = DefaultConnector;
+    let n_failed = probe_servers(&connector, config, &state).await
+        + probe_metadata(&connector, config, &state).await;
+
+    if let Err(err) = write_output(options, &state) {
+        error!("Failed to write databases: {}", err);
+
+        return 2;
+    }
+
+    if n_failed > 0 {
+        warn!("Probing finished with {} failures", n_failed);
+
+        1
+    } else {
+        0
+    }
+}
@@ -0,0 +1,4 @@
+mod policy;
+mod rule;
+
+pub use self::policy::RetentionPolicy;
@@ -0,0 +1,31 @@
+use crate::config::RetentionRule;
+use crate::state::DatabaseInfo;
+
+use super::rule::rule_matches;
+
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    rules: Vec<RetentionRule>,
+}
+
+impl RetentionPolicy {
+    pub fn new(rules: &[RetentionRule]) -> RetentionPolicy {
+        RetentionPolicy {
+            rules: rules.to_vec(),
+        }
+    }
+
+    pub fn flags(&self, database: &DatabaseInfo, now: i64) -> Vec<String> {
+        let mut flags: Vec<String> = Vec::new();
+
+        for rule in &self.rules {
+            let flag = rule.flag().to_lowercase();
+
+            if !flags.contains(&flag) && rule_matches(rule, database, now) {
+                flags.push(flag);
+            }
+        }
+
+        flags
+    }
+}
@@ -0,0 +1,43 @@
+use crate::config::RetentionRule;
+use crate::pattern;
+use crate::state::DatabaseInfo;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+pub fn rule_matches(rule: &RetentionRule, database: &DatabaseInfo, now: i64) -> bool {
+    if rule.no_metadata() && (database.commit().is_some() || database.branch_name().is_some()) {
+        return false;
+    }
+
+    if let Some(idle_days) = rule.idle_days() {
+        if now - database.last_active() < idle_days as i64 * SECONDS_PER_DAY {
+            return false;
+        }
+    }
+
+    if let Some(min_age_days) = rule.min_age_days() {
+        if now - database.created_or_first_seen() < min_age_days as i64 * SECONDS_PER_DAY {
+            return false;
+        }
+    }
+
+    if let Some(owner) = rule.owner() {
+        if database.database_owner().is_empty() || database.database_owner() != owner {
+            return false;
+        }
+    }
+
+    if let Some(name) = rule.name() {
+        if !pattern::matches(name, database.database_name()) {
+            return false;
+        }
+    }
+
+    if let Some(server) = rule.server() {
+        if !pattern::matches(server, database.server_name()) {
+            return false;
+        }
+    }
+
+    true
+}
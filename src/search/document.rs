@@ -1,7 +1,7 @@
 use super::Query;
 use super::SEPARATORS;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default)]
 pub struct Document {
     tokens: Vec<String>,
 }
@@ -44,10 +44,3 @@ impl Document {
     }
 }
 
-impl Default for Document {
-    fn default() -> Document {
-        Document {
-            tokens: Vec::default(),
-        }
-    }
-}
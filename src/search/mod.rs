@@ -1,7 +1,7 @@
 mod document;
 mod query;
 
-const SEPARATORS: &'static str = " _-";
+const SEPARATORS: &str = " _-";
 
 pub use self::document::Document;
 pub use self::query::Query;
@@ -1,30 +1,46 @@
 use super::SEPARATORS;
 
+const FLAG_PREFIX: &str = "flag:";
+
 #[derive(Debug)]
 pub struct Query {
     tokens: Vec<String>,
+    flags: Vec<String>,
 }
 
 impl Query {
     pub fn new(words: &[&str]) -> Query {
         let mut tokens = Vec::new();
+        let mut flags = Vec::new();
 
         for word in words {
             let word = word.to_lowercase();
 
-            tokens.extend(
-                word.split(|c| SEPARATORS.contains(c))
-                    .filter(|token| !token.is_empty())
-                    .map(|token| token.into()),
-            );
+            for part in word.split_whitespace() {
+                if let Some(flag) = part.strip_prefix(FLAG_PREFIX) {
+                    flags.push(flag.into());
+
+                    continue;
+                }
+
+                tokens.extend(
+                    part.split(|c| SEPARATORS.contains(c))
+                        .filter(|token| !token.is_empty())
+                        .map(|token| token.into()),
+                );
+            }
         }
 
-        Query { tokens }
+        Query { tokens, flags }
     }
 
     pub fn tokens(&self) -> &Vec<String> {
         &self.tokens
     }
+
+    pub fn flags(&self) -> &Vec<String> {
+        &self.flags
+    }
 }
 
 impl From<String> for Query {
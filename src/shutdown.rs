@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use tokio::sync::Notify;
+use tokio::time;
+
+const IDLE_POLL_INTERVAL: u64 = 50;
+
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    requested: Arc<AtomicBool>,
+    in_flight: Arc<AtomicUsize>,
+    notify: Arc<Notify>,
+}
+
+pub struct RequestGuard {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Shutdown {
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    pub async fn requested(&self) {
+        let notified = self.notify.notified();
+
+        if !self.is_requested() {
+            notified.await;
+        }
+    }
+
+    pub fn begin_request(&self) -> RequestGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+
+        RequestGuard {
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let in_flight = self.in_flight.load(Ordering::SeqCst);
+
+            if in_flight == 0 {
+                return true;
+            }
+
+            if Instant::now() >= deadline {
+                warn!("{} requests still in flight after {:?}", in_flight, timeout);
+
+                return false;
+            }
+
+            time::sleep(Duration::from_millis(IDLE_POLL_INTERVAL)).await;
+        }
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -1,15 +1,22 @@
-use search::Document;
+use crate::search::Document;
+use crate::search::Query;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DatabaseInfo {
     server_name: String,
     database_name: String,
     database_collate: String,
     database_owner: String,
     last_update: i64,
+    first_seen: i64,
+    server_created: Option<i64>,
+    metadata_created: Option<i64>,
+    backends: i64,
+    last_active: i64,
     commit: Option<i64>,
     project_name: Option<String>,
     branch_name: Option<String>,
+    flags: Vec<String>,
     document: Document,
 }
 
@@ -19,6 +26,7 @@ impl DatabaseInfo {
         database_name: &str,
         database_collate: &str,
         database_owner: &str,
+        backends: i64,
     ) -> DatabaseInfo {
         let document = Document::new(&[server_name, database_name]);
 
@@ -28,10 +36,16 @@ impl DatabaseInfo {
             database_collate: database_collate.into(),
             database_owner: database_owner.into(),
             last_update: 0,
+            first_seen: 0,
+            server_created: None,
+            metadata_created: None,
+            backends,
+            last_active: 0,
             commit: None,
             project_name: None,
             branch_name: None,
-            document: document,
+            flags: Vec::new(),
+            document,
         }
     }
 
@@ -55,6 +69,30 @@ impl DatabaseInfo {
         self.last_update
     }
 
+    pub fn first_seen(&self) -> i64 {
+        self.first_seen
+    }
+
+    pub fn server_created(&self) -> Option<i64> {
+        self.server_created
+    }
+
+    pub fn created(&self) -> Option<i64> {
+        self.metadata_created.or(self.server_created)
+    }
+
+    pub fn created_or_first_seen(&self) -> i64 {
+        self.created().unwrap_or(self.first_seen)
+    }
+
+    pub fn backends(&self) -> i64 {
+        self.backends
+    }
+
+    pub fn last_active(&self) -> i64 {
+        self.last_active
+    }
+
     pub fn commit(&self) -> &Option<i64> {
         &self.commit
     }
@@ -67,14 +105,54 @@ impl DatabaseInfo {
         &self.branch_name
     }
 
-    pub fn document(&self) -> &Document {
-        &self.document
+    pub fn flags(&self) -> &Vec<String> {
+        &self.flags
+    }
+
+    pub fn weight_for(&self, query: &Query) -> Option<usize> {
+        if !query.flags().iter().all(|flag| self.flags.contains(flag)) {
+            return None;
+        }
+
+        if query.tokens().is_empty() && !query.flags().is_empty() {
+            return Some(1);
+        }
+
+        self.document.weight_for(query)
     }
 
     pub fn set_last_update(&mut self, last_update: i64) {
         self.last_update = last_update;
     }
 
+    pub fn set_first_seen(&mut self, first_seen: i64) {
+        self.first_seen = first_seen;
+    }
+
+    pub fn set_last_active(&mut self, last_active: i64) {
+        self.last_active = last_active;
+    }
+
+    pub fn set_server_created(&mut self, created: Option<i64>) {
+        self.server_created = created;
+    }
+
+    pub fn set_metadata_created(&mut self, created: Option<i64>) {
+        self.metadata_created = created;
+    }
+
+    pub fn set_backends(&mut self, backends: i64, now: i64) {
+        self.backends = backends;
+
+        if backends > 0 || self.last_active == 0 {
+            self.last_active = now;
+        }
+    }
+
+    pub fn set_flags(&mut self, flags: Vec<String>) {
+        self.flags = flags;
+    }
+
     pub fn set_commit(&mut self, commit: i64) {
         self.commit = Some(commit);
     }
@@ -1,58 +1,198 @@
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::collections::HashSet;
+use std::sync::Arc;
 
-use time;
-
-use search::Query;
+use crate::retention::RetentionPolicy;
+use crate::search::Query;
 
 use super::DatabaseInfo;
+use super::SavedDatabase;
+use super::SavedDatabases;
+use super::ServerDetails;
 use super::ServerInfo;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct InternalState {
-    servers: HashMap<String, ServerInfo>,
-    databases: HashMap<String, HashMap<String, DatabaseInfo>>,
+    servers: HashMap<String, Arc<ServerInfo>>,
+    databases: HashMap<String, Arc<HashMap<String, DatabaseInfo>>>,
+    saved: Arc<SavedDatabases>,
+    retention: Arc<RetentionPolicy>,
     last_update: i64,
 }
 
 impl InternalState {
-    pub fn update_server(&mut self, server_name: &str, server_description: &Option<String>) {
+    pub fn new(retention: RetentionPolicy) -> InternalState {
+        InternalState {
+            servers: HashMap::default(),
+            databases: HashMap::default(),
+            saved: Arc::default(),
+            retention: Arc::new(retention),
+            last_update: 0,
+        }
+    }
+
+    pub fn update_server(
+        &mut self,
+        server_name: &str,
+        server_description: &Option<String>,
+        details: ServerDetails,
+    ) {
         let now = time::get_time().sec;
         let name = server_name.into();
 
-        self.servers
+        let server_info = self
+            .servers
             .entry(name)
-            .or_insert_with(|| ServerInfo::new(server_name, server_description));
+            .or_insert_with(|| Arc::new(ServerInfo::new(server_name, server_description)));
 
+        Arc::make_mut(server_info).set_details(details, now);
+
+        self.resolve_primaries();
         self.last_update = now;
     }
 
-    pub fn update_databases(&mut self, server_name: &str, databases: Vec<DatabaseInfo>) {
+    fn detect_primary(&self, server_info: &ServerInfo) -> Option<String> {
+        let details = match server_info.details() {
+            Some(details) if details.in_recovery() => details,
+            _ => return None,
+        };
+
+        if let Some(upstream) = details.upstream() {
+            if upstream != server_info.name() && self.servers.contains_key(upstream) {
+                return Some(upstream.clone());
+            }
+        }
+
+        self.servers
+            .values()
+            .filter(|candidate| candidate.name() != server_info.name())
+            .find(|candidate| match candidate.details() {
+                Some(details) => details
+                    .replicas()
+                    .iter()
+                    .any(|replica| replica.matches(server_info.name())),
+                None => false,
+            })
+            .map(|candidate| candidate.name().clone())
+    }
+
+    fn resolve_primaries(&mut self) {
+        let primaries: Vec<(String, Option<String>)> = self.servers
+            .values()
+            .map(|server_info| (server_info.name().clone(), self.detect_primary(server_info)))
+            .collect();
+
+        for (server_name, primary) in primaries {
+            if let Some(server_info) = self.servers.get_mut(&server_name) {
+                if server_info.primary() != &primary {
+                    Arc::make_mut(server_info).set_primary(primary);
+                }
+            }
+        }
+    }
+
+    pub fn replicas_of(&self, server_name: &str) -> Vec<String> {
+        let mut result: Vec<String> = self.servers
+            .values()
+            .filter(|server_info| match server_info.primary() {
+                Some(primary) => primary == server_name,
+                None => false,
+            })
+            .map(|server_info| server_info.name().clone())
+            .collect();
+        result.sort();
+
+        result
+    }
+
+    fn has_database(&self, server_name: &str, database_name: &str) -> bool {
+        self.databases
+            .get(server_name)
+            .is_some_and(|databases| databases.contains_key(database_name))
+    }
+
+    pub fn primary_database(&self, server_name: &str, database_name: &str) -> Option<&String> {
+        let primary = self.servers
+            .get(server_name)
+            .and_then(|server_info| server_info.primary().as_ref());
+
+        match primary {
+            Some(primary) if self.has_database(primary, database_name) => Some(primary),
+            _ => None,
+        }
+    }
+
+    pub fn database_replicas(&self, server_name: &str, database_name: &str) -> Vec<String> {
+        self.replicas_of(server_name)
+            .into_iter()
+            .filter(|replica| self.has_database(replica, database_name))
+            .collect()
+    }
+
+    pub fn update_databases(
+        &mut self,
+        server_name: &str,
+        databases: Vec<DatabaseInfo>,
+    ) -> (Vec<String>, Vec<String>) {
         let now = time::get_time().sec;
         let name = server_name.into();
-        let entry = self.databases
-            .entry(name)
-            .or_insert_with(|| HashMap::default());
+        let first_snapshot = !self.databases.contains_key(server_name);
+        let entry = Arc::make_mut(self.databases.entry(name).or_default());
         let mut keys: HashSet<_> = entry.keys().cloned().collect();
+        let mut created = Vec::new();
 
         for database in databases {
             let database_name = database.database_name().clone();
+            let backends = database.backends();
+            let server_created = database.server_created();
 
             keys.remove(&database_name);
-            entry
-                .entry(database_name)
-                .or_insert(database)
-                .set_last_update(now);
+
+            let database = match entry.entry(database_name) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    if !first_snapshot {
+                        created.push(entry.key().clone());
+                    }
+
+                    let saved = self
+                        .saved
+                        .get(server_name)
+                        .and_then(|databases| databases.get(entry.key()))
+                        .copied()
+                        .unwrap_or_else(|| SavedDatabase::new(now, 0));
+                    let database = entry.insert(database);
+                    database.set_first_seen(saved.first_seen());
+                    database.set_last_active(saved.last_active());
+                    database
+                }
+            };
+            database.set_last_update(now);
+            database.set_backends(backends, now);
+            database.set_server_created(server_created);
         }
 
-        for key in keys {
-            entry.remove(&key);
+        for key in &keys {
+            entry.remove(key);
+        }
+
+        for database in entry.values_mut() {
+            let flags = self.retention.flags(database, now);
+
+            database.set_flags(flags);
         }
 
         self.last_update = now;
+
+        let mut dropped: Vec<String> = keys.into_iter().collect();
+        created.sort();
+        dropped.sort();
+
+        (created, dropped)
     }
 
-    pub fn query(&self, query: &Query, callback: &mut FnMut(&ServerInfo, &DatabaseInfo, usize)) {
+    pub fn query(&self, query: &Query, callback: &mut dyn FnMut(&ServerInfo, &DatabaseInfo, usize)) {
         for (server_name, databases) in &self.databases {
             let server_info = match self.servers.get(server_name) {
                 Some(server_info) => server_info,
@@ -64,16 +204,14 @@ impl InternalState {
             };
 
             for database_info in databases.values() {
-                let document = database_info.document();
-
-                if let Some(weight) = document.weight_for(query) {
+                if let Some(weight) = database_info.weight_for(query) {
                     callback(server_info, database_info, weight);
                 }
             }
         }
     }
 
-    pub fn for_each_database(&self, callback: &mut FnMut(&ServerInfo, &DatabaseInfo)) {
+    pub fn for_each_database(&self, callback: &mut dyn FnMut(&ServerInfo, &DatabaseInfo)) {
         for (server_name, databases) in &self.databases {
             let server_info = match self.servers.get(server_name) {
                 Some(server_info) => server_info,
@@ -90,6 +228,47 @@ impl InternalState {
         }
     }
 
+    pub fn for_each_server(&self, callback: &mut dyn FnMut(&ServerInfo, usize)) {
+        for (server_name, server_info) in &self.servers {
+            let n_databases = self.databases
+                .get(server_name)
+                .map(|databases| databases.len())
+                .unwrap_or(0);
+
+            callback(server_info, n_databases);
+        }
+    }
+
+    pub fn server(&self, server_name: &str) -> Option<(&ServerInfo, usize)> {
+        let n_databases = self.databases
+            .get(server_name)
+            .map(|databases| databases.len())
+            .unwrap_or(0);
+
+        self.servers
+            .get(server_name)
+            .map(|server_info| (server_info.as_ref(), n_databases))
+    }
+
+    pub fn for_each_server_database(
+        &self,
+        server_name: &str,
+        callback: &mut dyn FnMut(&ServerInfo, &DatabaseInfo),
+    ) -> bool {
+        let server_info = match self.servers.get(server_name) {
+            Some(server_info) => server_info,
+            None => return false,
+        };
+
+        if let Some(databases) = self.databases.get(server_name) {
+            for database_info in databases.values() {
+                callback(server_info, database_info);
+            }
+        }
+
+        true
+    }
+
     pub fn set_database_metadata(
         &mut self,
         server_name: &str,
@@ -97,24 +276,67 @@ impl InternalState {
         commit: i64,
         branch_name: &str,
         project_name: &str,
+        created: Option<i64>,
     ) {
-        if let Some(databases) = self.databases.get_mut(server_name) {
-            for database in databases.values_mut() {
-                if database.database_name() == database_name {
-                    database.set_commit(commit);
-                    database.set_branch_name(branch_name);
-                    database.set_project_name(project_name);
+        let databases = match self.databases.get_mut(server_name) {
+            Some(databases) if databases.contains_key(database_name) => Arc::make_mut(databases),
+            _ => return,
+        };
 
-                    let commit = format!("{}", commit);
+        if let Some(database) = databases.get_mut(database_name) {
+            database.set_commit(commit);
+            database.set_metadata_created(created);
+            database.set_branch_name(branch_name);
+            database.set_project_name(project_name);
 
-                    database
-                        .document_mut()
-                        .extend(&[&commit, branch_name, project_name]);
+            let commit = format!("{}", commit);
 
-                    break;
-                }
+            database
+                .document_mut()
+                .extend(&[&commit, branch_name, project_name]);
+
+            let flags = self.retention.flags(database, time::get_time().sec);
+            database.set_flags(flags);
+        }
+    }
+
+    pub fn remove_database(&mut self, server_name: &str, database_name: &str) -> bool {
+        match self.databases.get_mut(server_name) {
+            Some(databases) if databases.contains_key(database_name) => {
+                Arc::make_mut(databases).remove(database_name).is_some()
             }
+            _ => false,
+        }
+    }
+
+    pub fn saved(&self) -> &SavedDatabases {
+        &self.saved
+    }
+
+    pub fn set_saved(&mut self, saved: SavedDatabases) {
+        self.saved = Arc::new(saved);
+    }
+
+    pub fn remember_databases(&mut self, server_name: &str) -> bool {
+        let databases: HashMap<String, SavedDatabase> = match self.databases.get(server_name) {
+            Some(databases) => databases
+                .iter()
+                .map(|(name, database)| {
+                    let saved = SavedDatabase::new(database.first_seen(), database.last_active());
+
+                    (name.clone(), saved)
+                })
+                .collect(),
+            None => return false,
+        };
+
+        if self.saved.get(server_name) == Some(&databases) {
+            return false;
         }
+
+        Arc::make_mut(&mut self.saved).insert(server_name.into(), databases);
+
+        true
     }
 
     pub fn last_update(&self) -> i64 {
@@ -124,10 +346,6 @@ impl InternalState {
 
 impl Default for InternalState {
     fn default() -> InternalState {
-        InternalState {
-            servers: HashMap::default(),
-            databases: HashMap::default(),
-            last_update: 0,
-        }
+        InternalState::new(RetentionPolicy::default())
     }
 }
@@ -1,12 +1,28 @@
 mod database;
 mod internal;
+mod replica;
+mod role;
 mod row;
+mod saved_database;
 mod server;
+mod server_details;
+mod server_row;
+#[allow(clippy::module_inception)]
 mod state;
+mod state_file;
+mod visibility;
 
 use self::internal::InternalState;
-use self::server::ServerInfo;
 
 pub use self::database::DatabaseInfo;
+pub use self::replica::ReplicaInfo;
+pub use self::role::RoleInfo;
 pub use self::row::DatabaseRow;
+pub use self::saved_database::SavedDatabase;
+pub use self::server::ServerInfo;
+pub use self::server_details::ServerDetails;
+pub use self::server_row::ServerRow;
 pub use self::state::State;
+pub use self::state_file::SavedDatabases;
+pub use self::state_file::StateFile;
+pub use self::visibility::Visibility;
@@ -0,0 +1,52 @@
+#[derive(Debug, Clone)]
+pub struct ReplicaInfo {
+    application_name: String,
+    client_addr: Option<String>,
+    client_hostname: Option<String>,
+    state: String,
+    lag_bytes: Option<i64>,
+}
+
+impl ReplicaInfo {
+    pub fn new(
+        application_name: &str,
+        client_addr: &Option<String>,
+        client_hostname: &Option<String>,
+        state: &str,
+        lag_bytes: Option<i64>,
+    ) -> ReplicaInfo {
+        ReplicaInfo {
+            application_name: application_name.into(),
+            client_addr: client_addr.clone(),
+            client_hostname: client_hostname.clone(),
+            state: state.into(),
+            lag_bytes,
+        }
+    }
+
+    pub fn application_name(&self) -> &String {
+        &self.application_name
+    }
+
+    pub fn client_addr(&self) -> &Option<String> {
+        &self.client_addr
+    }
+
+    pub fn client_hostname(&self) -> &Option<String> {
+        &self.client_hostname
+    }
+
+    pub fn state(&self) -> &String {
+        &self.state
+    }
+
+    pub fn lag_bytes(&self) -> Option<i64> {
+        self.lag_bytes
+    }
+
+    pub fn matches(&self, server_name: &str) -> bool {
+        self.application_name == server_name
+            || self.client_hostname.as_ref().is_some_and(|name| name == server_name)
+            || self.client_addr.as_ref().is_some_and(|addr| addr == server_name)
+    }
+}
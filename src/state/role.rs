@@ -0,0 +1,28 @@
+#[derive(Debug, Clone)]
+pub struct RoleInfo {
+    name: String,
+    superuser: bool,
+    login: bool,
+}
+
+impl RoleInfo {
+    pub fn new(name: &str, superuser: bool, login: bool) -> RoleInfo {
+        RoleInfo {
+            name: name.into(),
+            superuser,
+            login,
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn superuser(&self) -> bool {
+        self.superuser
+    }
+
+    pub fn login(&self) -> bool {
+        self.login
+    }
+}
@@ -9,6 +9,10 @@ pub struct DatabaseRow {
     branch_name: Option<String>,
     project_name: Option<String>,
     last_update: i64,
+    first_seen: i64,
+    created: Option<i64>,
+    flags: Vec<String>,
+    replicas: Vec<String>,
     weight: usize,
 }
 
@@ -19,11 +23,6 @@ impl DatabaseRow {
         database_name: &str,
         database_collate: &str,
         database_owner: &str,
-        commit: &Option<i64>,
-        branch_name: &Option<String>,
-        project_name: &Option<String>,
-        last_update: i64,
-        weight: usize,
     ) -> Self {
         DatabaseRow {
             server_name: server_name.into(),
@@ -31,14 +30,47 @@ impl DatabaseRow {
             database_name: database_name.into(),
             database_collate: database_collate.into(),
             database_owner: database_owner.into(),
-            commit: commit.clone(),
-            branch_name: branch_name.clone(),
-            project_name: project_name.clone(),
-            last_update,
-            weight,
+            commit: None,
+            branch_name: None,
+            project_name: None,
+            last_update: 0,
+            first_seen: 0,
+            created: None,
+            flags: Vec::new(),
+            replicas: Vec::new(),
+            weight: 0,
         }
     }
 
+    pub fn with_metadata(
+        mut self,
+        commit: &Option<i64>,
+        branch_name: &Option<String>,
+        project_name: &Option<String>,
+    ) -> Self {
+        self.commit = *commit;
+        self.branch_name = branch_name.clone();
+        self.project_name = project_name.clone();
+        self
+    }
+
+    pub fn with_times(mut self, last_update: i64, first_seen: i64, created: Option<i64>) -> Self {
+        self.last_update = last_update;
+        self.first_seen = first_seen;
+        self.created = created;
+        self
+    }
+
+    pub fn with_flags(mut self, flags: &[String]) -> Self {
+        self.flags = flags.to_vec();
+        self
+    }
+
+    pub fn with_weight(mut self, weight: usize) -> Self {
+        self.weight = weight;
+        self
+    }
+
     pub fn server_name(&self) -> &String {
         &self.server_name
     }
@@ -75,6 +107,30 @@ impl DatabaseRow {
         self.last_update
     }
 
+    pub fn first_seen(&self) -> i64 {
+        self.first_seen
+    }
+
+    pub fn created(&self) -> Option<i64> {
+        self.created
+    }
+
+    pub fn created_or_first_seen(&self) -> i64 {
+        self.created.unwrap_or(self.first_seen)
+    }
+
+    pub fn flags(&self) -> &Vec<String> {
+        &self.flags
+    }
+
+    pub fn replicas(&self) -> &Vec<String> {
+        &self.replicas
+    }
+
+    pub fn set_replicas(&mut self, replicas: Vec<String>) {
+        self.replicas = replicas;
+    }
+
     pub fn weight(&self) -> usize {
         self.weight
     }
@@ -0,0 +1,22 @@
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedDatabase {
+    first_seen: i64,
+    last_active: i64,
+}
+
+impl SavedDatabase {
+    pub fn new(first_seen: i64, last_active: i64) -> SavedDatabase {
+        SavedDatabase {
+            first_seen,
+            last_active,
+        }
+    }
+
+    pub fn first_seen(&self) -> i64 {
+        self.first_seen
+    }
+
+    pub fn last_active(&self) -> i64 {
+        self.last_active
+    }
+}
@@ -1,7 +1,12 @@
-#[derive(Debug)]
+use super::ServerDetails;
+
+#[derive(Debug, Clone)]
 pub struct ServerInfo {
     name: String,
     description: Option<String>,
+    details: Option<ServerDetails>,
+    last_update: i64,
+    primary: Option<String>,
 }
 
 impl ServerInfo {
@@ -9,6 +14,9 @@ impl ServerInfo {
         ServerInfo {
             name: name.into(),
             description: description.clone(),
+            details: None,
+            last_update: 0,
+            primary: None,
         }
     }
 
@@ -19,4 +27,25 @@ impl ServerInfo {
     pub fn description(&self) -> &Option<String> {
         &self.description
     }
+
+    pub fn details(&self) -> &Option<ServerDetails> {
+        &self.details
+    }
+
+    pub fn last_update(&self) -> i64 {
+        self.last_update
+    }
+
+    pub fn set_details(&mut self, details: ServerDetails, last_update: i64) {
+        self.details = Some(details);
+        self.last_update = last_update;
+    }
+
+    pub fn primary(&self) -> &Option<String> {
+        &self.primary
+    }
+
+    pub fn set_primary(&mut self, primary: Option<String>) {
+        self.primary = primary;
+    }
 }
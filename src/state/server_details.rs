@@ -0,0 +1,86 @@
+use super::ReplicaInfo;
+use super::RoleInfo;
+
+#[derive(Debug, Clone)]
+pub struct ServerDetails {
+    version: String,
+    start_time: i64,
+    in_recovery: bool,
+    cluster_size: Option<i64>,
+    max_connections: i64,
+    roles: Vec<RoleInfo>,
+    upstream: Option<String>,
+    replication_lag: Option<i64>,
+    replicas: Vec<ReplicaInfo>,
+}
+
+impl ServerDetails {
+    pub fn new(
+        version: &str,
+        start_time: i64,
+        in_recovery: bool,
+        cluster_size: Option<i64>,
+        max_connections: i64,
+        roles: Vec<RoleInfo>,
+    ) -> ServerDetails {
+        ServerDetails {
+            version: version.into(),
+            start_time,
+            in_recovery,
+            cluster_size,
+            max_connections,
+            roles,
+            upstream: None,
+            replication_lag: None,
+            replicas: Vec::new(),
+        }
+    }
+
+    pub fn with_replication(
+        mut self,
+        upstream: Option<String>,
+        replication_lag: Option<i64>,
+        replicas: Vec<ReplicaInfo>,
+    ) -> ServerDetails {
+        self.upstream = upstream;
+        self.replication_lag = replication_lag;
+        self.replicas = replicas;
+        self
+    }
+
+    pub fn version(&self) -> &String {
+        &self.version
+    }
+
+    pub fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    pub fn in_recovery(&self) -> bool {
+        self.in_recovery
+    }
+
+    pub fn cluster_size(&self) -> Option<i64> {
+        self.cluster_size
+    }
+
+    pub fn max_connections(&self) -> i64 {
+        self.max_connections
+    }
+
+    pub fn roles(&self) -> &Vec<RoleInfo> {
+        &self.roles
+    }
+
+    pub fn upstream(&self) -> &Option<String> {
+        &self.upstream
+    }
+
+    pub fn replication_lag(&self) -> Option<i64> {
+        self.replication_lag
+    }
+
+    pub fn replicas(&self) -> &Vec<ReplicaInfo> {
+        &self.replicas
+    }
+}
@@ -0,0 +1,57 @@
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct ServerRow {
+    name: String,
+    description: Option<String>,
+    n_databases: usize,
+    primary: Option<String>,
+    replication_lag: Option<i64>,
+    replicas: Vec<String>,
+}
+
+impl ServerRow {
+    pub fn new(name: &str, description: &Option<String>, n_databases: usize) -> Self {
+        ServerRow {
+            name: name.into(),
+            description: description.clone(),
+            n_databases,
+            primary: None,
+            replication_lag: None,
+            replicas: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn n_databases(&self) -> usize {
+        self.n_databases
+    }
+
+    pub fn primary(&self) -> &Option<String> {
+        &self.primary
+    }
+
+    pub fn replication_lag(&self) -> Option<i64> {
+        self.replication_lag
+    }
+
+    pub fn replicas(&self) -> &Vec<String> {
+        &self.replicas
+    }
+
+    pub fn set_replication(
+        &mut self,
+        primary: &Option<String>,
+        replication_lag: Option<i64>,
+        replicas: Vec<String>,
+    ) {
+        self.primary = primary.clone();
+        self.replication_lag = replication_lag;
+        self.replicas = replicas;
+    }
+}
@@ -1,104 +1,302 @@
+use std::io::Result as IoResult;
 use std::sync::Arc;
-use std::sync::RwLock;
+use std::sync::Mutex;
 
-use search::Query;
+use arc_swap::ArcSwap;
+use tokio::sync::broadcast::Receiver;
+
+use database_monitor_api::Event;
+
+use crate::events::EventBus;
+use crate::retention::RetentionPolicy;
+use crate::search::Query;
+use crate::worker::DatabaseMetadata;
 
 use super::DatabaseInfo;
 use super::DatabaseRow;
+use super::StateFile;
 use super::InternalState;
+use super::ServerDetails;
 use super::ServerInfo;
+use super::ServerRow;
+use super::Visibility;
 
 #[derive(Debug, Clone)]
 pub struct State {
-    inner: Arc<RwLock<InternalState>>,
+    snapshot: Arc<ArcSwap<InternalState>>,
+    writer: Arc<Mutex<()>>,
+    events: EventBus,
+    state_file: Arc<StateFile>,
 }
 
 impl State {
+    pub fn new(retention: RetentionPolicy) -> State {
+        State {
+            snapshot: Arc::new(ArcSwap::from_pointee(InternalState::new(retention))),
+            writer: Arc::new(Mutex::new(())),
+            events: EventBus::new(),
+            state_file: Arc::default(),
+        }
+    }
+
+    pub fn open(retention: RetentionPolicy, state_file: &Option<String>) -> IoResult<State> {
+        let state_file = StateFile::new(state_file);
+        let mut inner = InternalState::new(retention);
+
+        inner.set_saved(state_file.load()?);
+
+        Ok(State {
+            snapshot: Arc::new(ArcSwap::from_pointee(inner)),
+            writer: Arc::new(Mutex::new(())),
+            events: EventBus::new(),
+            state_file: Arc::new(state_file),
+        })
+    }
+
+    fn snapshot(&self) -> Arc<InternalState> {
+        self.snapshot.load_full()
+    }
+
+    fn update<F, R>(&self, update: F) -> R
+    where
+        F: FnOnce(&mut InternalState) -> R,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(|err| {
+            warn!("Recovering state writer lock poisoned by a failed update");
+
+            err.into_inner()
+        });
+        let mut next = InternalState::clone(&self.snapshot.load());
+
+        let result = update(&mut next);
+        self.snapshot.store(Arc::new(next));
+
+        result
+    }
+
+    pub fn subscribe(&self) -> Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    pub fn publish(&self, event: Event) {
+        self.events.publish(event);
+    }
+
     pub fn update_server(
         &self,
         server_name: &str,
         server_description: &Option<String>,
+        details: ServerDetails,
         databases: Vec<DatabaseInfo>,
     ) {
-        if let Ok(mut inner) = self.inner.write() {
-            inner.update_server(server_name, server_description);
-            inner.update_databases(server_name, databases);
-        } else {
-            warn!("Failed to lock state for write");
+        let (created, dropped) = self.update(|inner| {
+            inner.update_server(server_name, server_description, details);
+
+            let result = inner.update_databases(server_name, databases);
+
+            self.save_databases(inner, server_name);
+
+            result
+        });
+
+        for database_name in created {
+            self.publish(Event::database_created(server_name, &database_name));
+        }
+
+        for database_name in dropped {
+            self.publish(Event::database_dropped(server_name, &database_name));
         }
     }
 
-    pub fn query(&self, query: &Query) -> Vec<DatabaseRow> {
+    pub fn query(
+        &self,
+        query: &Query,
+        visibility: &Visibility,
+        include_replicas: bool,
+    ) -> Vec<DatabaseRow> {
         let mut result = Vec::new();
+        let inner = self.snapshot();
 
-        if let Ok(inner) = self.inner.read() {
-            inner.query(query, &mut |server, database, weight| {
-                let row = DatabaseRow::new(
-                    server.name(),
-                    server.description(),
-                    database.database_name(),
-                    database.database_collate(),
-                    database.database_owner(),
-                    database.commit(),
-                    database.project_name(),
-                    database.branch_name(),
-                    database.last_update(),
-                    weight,
-                );
+        inner.query(query, &mut |server, database, weight| {
+            if !visibility.is_visible(server.name()) {
+                return;
+            }
+
+            if !include_replicas {
+                let primary = inner.primary_database(server.name(), database.database_name());
+
+                if primary.is_some_and(|primary| visibility.is_visible(primary)) {
+                    return;
+                }
+            }
+
+            let replicas = inner
+                .database_replicas(server.name(), database.database_name())
+                .into_iter()
+                .filter(|replica| visibility.is_visible(replica))
+                .collect();
+            let mut row = Self::database_row(server, database, weight);
+            row.set_replicas(replicas);
+
+            result.push(row);
+        });
+
+        result
+    }
+
+    pub fn servers(&self, visibility: &Visibility) -> Vec<ServerRow> {
+        let mut result = Vec::new();
+        let inner = self.snapshot();
+
+        inner.for_each_server(&mut |server, n_databases| {
+            if visibility.is_visible(server.name()) {
+                let replicas = inner
+                    .replicas_of(server.name())
+                    .into_iter()
+                    .filter(|replica| visibility.is_visible(replica))
+                    .collect();
+                let replication_lag = server
+                    .details()
+                    .as_ref()
+                    .and_then(|details| details.replication_lag());
+                let mut row = ServerRow::new(server.name(), server.description(), n_databases);
+                row.set_replication(server.primary(), replication_lag, replicas);
 
                 result.push(row);
-            });
-        } else {
-            warn!("Failed to lock state for read");
-        }
+            }
+        });
 
         result
     }
 
-    pub fn for_each_database(&self, callback: &mut FnMut(&ServerInfo, &DatabaseInfo)) {
-        if let Ok(inner) = self.inner.read() {
-            inner.for_each_database(callback);
-        } else {
-            warn!("Failed to lock state for read");
+    pub fn server(
+        &self,
+        server_name: &str,
+        visibility: &Visibility,
+    ) -> Option<(ServerInfo, usize, Vec<String>)> {
+        if !visibility.is_visible(server_name) {
+            return None;
         }
+
+        let inner = self.snapshot();
+
+        inner.server(server_name).map(|(server_info, n_databases)| {
+            let replicas = inner
+                .replicas_of(server_name)
+                .into_iter()
+                .filter(|replica| visibility.is_visible(replica))
+                .collect();
+
+            (server_info.clone(), n_databases, replicas)
+        })
     }
 
-    pub fn set_database_metadata(
+    pub fn server_databases(
         &self,
         server_name: &str,
-        database_name: &str,
-        commit: i64,
-        branch_name: &str,
-        project_name: &str,
-    ) {
-        if let Ok(mut inner) = self.inner.write() {
-            inner.set_database_metadata(
-                server_name,
-                database_name,
-                commit,
-                branch_name,
-                project_name,
-            );
+        visibility: &Visibility,
+    ) -> Option<Vec<DatabaseRow>> {
+        let mut result = Vec::new();
+
+        if !visibility.is_visible(server_name) {
+            return None;
+        }
+
+        let found =
+            self.snapshot()
+                .for_each_server_database(server_name, &mut |server, database| {
+                    result.push(Self::database_row(server, database, 0));
+                });
+
+        if found {
+            Some(result)
         } else {
-            warn!("Failed to lock state for write");
+            None
         }
     }
 
-    pub fn last_update(&self) -> Option<i64> {
-        if let Ok(inner) = self.inner.read() {
-            Some(inner.last_update())
-        } else {
-            warn!("Failed to lock state for read");
+    pub fn databases(&self) -> Vec<DatabaseRow> {
+        let mut result = Vec::new();
 
-            None
+        self.for_each_database(&mut |server, database| {
+            result.push(Self::database_row(server, database, 0));
+        });
+
+        result
+    }
+
+    fn database_row(server: &ServerInfo, database: &DatabaseInfo, weight: usize) -> DatabaseRow {
+        DatabaseRow::new(
+            server.name(),
+            server.description(),
+            database.database_name(),
+            database.database_collate(),
+            database.database_owner(),
+        )
+        .with_metadata(
+            database.commit(),
+            database.branch_name(),
+            database.project_name(),
+        )
+        .with_times(
+            database.last_update(),
+            database.first_seen(),
+            database.created(),
+        )
+        .with_flags(database.flags())
+        .with_weight(weight)
+    }
+
+    pub fn for_each_database(&self, callback: &mut dyn FnMut(&ServerInfo, &DatabaseInfo)) {
+        self.snapshot().for_each_database(callback);
+    }
+
+    pub fn set_databases_metadata(&self, metadata: &[DatabaseMetadata]) {
+        self.update(|inner| {
+            for database in metadata {
+                inner.set_database_metadata(
+                    database.server_name(),
+                    database.database_name(),
+                    database.commit(),
+                    database.branch_name(),
+                    database.project_name(),
+                    database.created(),
+                );
+            }
+        });
+    }
+
+    pub fn remove_database(&self, server_name: &str, database_name: &str) {
+        let removed = self.update(|inner| {
+            let removed = inner.remove_database(server_name, database_name);
+
+            self.save_databases(inner, server_name);
+
+            removed
+        });
+
+        if removed {
+            self.publish(Event::database_dropped(server_name, database_name));
         }
     }
+
+    fn save_databases(&self, inner: &mut InternalState, server_name: &str) {
+        if !inner.remember_databases(server_name) {
+            return;
+        }
+
+        if let Err(err) = self.state_file.save(inner.saved()) {
+            warn!("Failed to save state file: {}", err);
+        }
+    }
+
+    pub fn last_update(&self) -> i64 {
+        self.snapshot().last_update()
+    }
 }
 
 impl Default for State {
     fn default() -> Self {
-        State {
-            inner: Arc::new(RwLock::new(InternalState::default())),
-        }
+        State::new(RetentionPolicy::default())
     }
 }
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+
+use super::SavedDatabase;
+
+pub type SavedDatabases = HashMap<String, HashMap<String, SavedDatabase>>;
+
+#[derive(Debug, Clone, Default)]
+pub struct StateFile {
+    path: Option<String>,
+}
+
+impl StateFile {
+    pub fn new(path: &Option<String>) -> StateFile {
+        StateFile { path: path.clone() }
+    }
+
+    pub fn load(&self) -> IoResult<SavedDatabases> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(SavedDatabases::new()),
+        };
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(ref err) if err.kind() == ErrorKind::NotFound => return Ok(SavedDatabases::new()),
+            Err(err) => return Err(err),
+        };
+
+        serde_json::from_str(&content)
+            .map_err(|err| IoError::new(ErrorKind::InvalidData, format!("{}: {}", path, err)))
+    }
+
+    pub fn save(&self, databases: &SavedDatabases) -> IoResult<()> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+        let temporary = format!("{}.tmp", path);
+        let content = serde_json::to_string(databases)?;
+
+        fs::write(&temporary, content)?;
+        fs::rename(&temporary, path)
+    }
+}
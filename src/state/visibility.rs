@@ -0,0 +1,18 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Visibility {
+    #[default]
+    All,
+    Servers(HashSet<String>),
+}
+
+impl Visibility {
+    pub fn is_visible(&self, server_name: &str) -> bool {
+        match *self {
+            Visibility::All => true,
+            Visibility::Servers(ref servers) => servers.contains(server_name),
+        }
+    }
+}
+
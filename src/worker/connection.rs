@@ -0,0 +1,30 @@
+use tokio_postgres::Client;
+use tokio_postgres::Config;
+use tokio_postgres::Error as PgError;
+use tokio_postgres::NoTls;
+
+pub async fn connect_postgres(
+    host: &str,
+    port: u16,
+    user: &str,
+    password: &str,
+    database: &str,
+) -> Result<Client, PgError> {
+    let (client, connection) = Config::new()
+        .host(host)
+        .port(port)
+        .user(user)
+        .password(password)
+        .dbname(database)
+        .connect(NoTls)
+        .await?;
+    let host = host.to_string();
+
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            debug!("Connection to {}:{} closed: {}", host, port, err);
+        }
+    });
+
+    Ok(client)
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::config::MetadataConnInfo;
+use crate::config::ServerConnInfo;
+use crate::state::DatabaseInfo;
+use crate::state::ServerDetails;
+
+use crate::worker::connect_postgres;
+use crate::worker::probe_server;
+use crate::worker::DatabaseMetadata;
+use crate::worker::ServerDatabase;
+use crate::worker::WorkerResult;
+
+use super::Connector;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultConnector;
+
+#[async_trait]
+impl Connector for DefaultConnector {
+    async fn probe_server(
+        &self,
+        connection_info: &ServerConnInfo,
+        timeout: Duration,
+    ) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)> {
+        probe_server(connection_info, timeout).await
+    }
+
+    async fn query_metadata(
+        &self,
+        connection_info: &MetadataConnInfo,
+        databases: &[ServerDatabase],
+    ) -> WorkerResult<Vec<DatabaseMetadata>> {
+        let client = connect_postgres(
+            connection_info.host(),
+            connection_info.port(),
+            connection_info.role(),
+            connection_info.password(),
+            connection_info.database(),
+        )
+        .await?;
+        let statement = client.prepare(connection_info.query()).await?;
+        let mut result = Vec::default();
+
+        for database in databases {
+            let server_name = database.server_name();
+            let database_name = database.database_name();
+
+            debug!("Updating database {}/{}", server_name, database_name);
+
+            for row in client
+                .query(&statement, &[server_name, database_name])
+                .await?
+            {
+                let commit: i64 = row.get(0);
+                let branch_name: String = row.get(1);
+                let project_name: String = row.get(2);
+                let created = if row.len() > 3 {
+                    row.try_get::<_, Option<i64>>(3).unwrap_or_else(|err| {
+                        warn!(
+                            "Ignoring creation time of {}/{}: {}",
+                            server_name, database_name, err
+                        );
+
+                        None
+                    })
+                } else {
+                    None
+                };
+
+                result.push(DatabaseMetadata::new(
+                    server_name,
+                    database_name,
+                    commit,
+                    &branch_name,
+                    &project_name,
+                    created,
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+}
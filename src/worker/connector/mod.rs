@@ -0,0 +1,5 @@
+mod default;
+mod traits;
+
+pub use self::default::DefaultConnector;
+pub use self::traits::Connector;
@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::config::MetadataConnInfo;
+use crate::config::ServerConnInfo;
+use crate::state::DatabaseInfo;
+use crate::state::ServerDetails;
+use crate::worker::DatabaseMetadata;
+use crate::worker::ServerDatabase;
+use crate::worker::WorkerResult;
+
+#[async_trait]
+pub trait Connector: Send + Sync {
+    async fn probe_server(
+        &self,
+        connection_info: &ServerConnInfo,
+        timeout: Duration,
+    ) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)>;
+
+    async fn query_metadata(
+        &self,
+        connection_info: &MetadataConnInfo,
+        databases: &[ServerDatabase],
+    ) -> WorkerResult<Vec<DatabaseMetadata>>;
+}
@@ -1,97 +1,246 @@
-use std::io::Result as IoResult;
-use std::thread::Builder;
-use std::thread::JoinHandle;
-use std::thread;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
-use postgres::Connection;
-use postgres::TlsMode;
+use futures::future;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time;
 
-use config::ServerConnInfo;
-use state::DatabaseInfo;
-use state::State;
+use database_monitor_api::Event;
 
-use super::WorkerResult;
+use crate::config::ServerConnInfo;
+use crate::config::TimeOfDay;
+use crate::state::State;
+
+use super::Cancellation;
+use super::Connector;
+use super::Schedule;
+use super::WorkerMessage;
+use super::WorkerTask;
 
 pub struct DatabaseWorker {
-    join_handle: JoinHandle<()>,
+    task: WorkerTask,
+}
+
+struct Probe<'a> {
+    connector: &'a dyn Connector,
+    state: &'a State,
+    timeout: Duration,
+    cancellation: &'a Cancellation,
 }
 
-fn server_database_infos(connection_info: &ServerConnInfo) -> WorkerResult<Vec<DatabaseInfo>> {
-    let url = format!(
-        "postgresql://{2}:{3}@{0}:{1}/postgres",
-        connection_info.host(),
-        connection_info.port(),
-        connection_info.role(),
-        connection_info.password()
-    );
-    let conn = Connection::connect(url, TlsMode::None)?;
-    let rows = conn.query(include_str!("query-databases.sql"), &[])?;
-
-    let result = rows.into_iter()
-        .map(|row| {
-            let database_name: String = row.get(0);
-            let collation_name: String = row.get(1);
-            let owner: String = row.get(2);
-
-            DatabaseInfo::new(
+async fn update_server(
+    connector: &dyn Connector,
+    connection_info: &ServerConnInfo,
+    state: &State,
+    timeout: Duration,
+) -> Result<(), String> {
+    debug!("Updating server {}", connection_info.host());
+
+    match connector.probe_server(connection_info, timeout).await {
+        Ok((details, dbs)) => {
+            state.update_server(
                 connection_info.host(),
-                &database_name,
-                &collation_name,
-                &owner,
-            )
-        })
-        .collect();
+                connection_info.description(),
+                details,
+                dbs,
+            );
+
+            Ok(())
+        }
+        Err(err) => {
+            warn!(
+                "Failed to update server {}: {}",
+                connection_info.host(),
+                err
+            );
 
-    Ok(result)
+            Err(err.to_string())
+        }
+    }
 }
 
-fn do_work(servers: Vec<ServerConnInfo>, interval: Duration, state: State) {
-    loop {
-        info!("Updating servers started");
-
-        for connection_info in &servers {
-            debug!("Updating server {}", connection_info.host());
-
-            match server_database_infos(connection_info) {
-                Ok(dbs) => state.update_server(
-                    &connection_info.host(),
-                    &connection_info.description(),
-                    dbs,
-                ),
-                Err(err) => warn!(
-                    "Failed to update server {}: {}",
-                    connection_info.host(),
-                    err
-                ),
-            }
+async fn update_server_timeout(
+    connector: &dyn Connector,
+    connection_info: &ServerConnInfo,
+    state: &State,
+    timeout: Duration,
+) -> Result<(), String> {
+    let update = update_server(connector, connection_info, state, timeout);
+
+    match time::timeout(timeout, update).await {
+        Ok(result) => result,
+        Err(_) => {
+            warn!(
+                "Probe of server {} timed out after {:?}",
+                connection_info.host(),
+                timeout
+            );
+
+            Err(format!("Probe timed out after {} seconds", timeout.as_secs()))
+        }
+    }
+}
+
+async fn update_servers<'a, I>(
+    probe: &Probe<'_>,
+    servers: I,
+    health: &mut HashMap<String, bool>,
+) where
+    I: IntoIterator<Item = &'a ServerConnInfo>,
+{
+    let state = probe.state;
+    let updates = servers.into_iter().map(|connection_info| async move {
+        let result =
+            update_server_timeout(probe.connector, connection_info, state, probe.timeout).await;
+
+        (connection_info.host(), result)
+    });
+    let results = tokio::select! {
+        results = future::join_all(updates) => results,
+        _ = probe.cancellation.cancelled() => {
+            info!("Server probes cancelled by shutdown");
+
+            return;
+        }
+    };
+
+    if results.is_empty() {
+        return;
+    }
+
+    for (server_name, result) in results {
+        let healthy = result.is_ok();
+
+        if health.insert(server_name.clone(), healthy) != Some(healthy) {
+            state.publish(Event::server_health(server_name, healthy, result.err()));
+        }
+    }
+
+    state.publish(Event::refresh_finished(state.last_update()));
+}
+
+fn notify_metadata(metadata_sender: &Option<UnboundedSender<WorkerMessage>>) {
+    if let Some(sender) = metadata_sender {
+        if let Err(err) = sender.send(WorkerMessage::RefreshAll) {
+            warn!("Failed to notify metadata worker: {}", err);
+        }
+    }
+}
+
+fn is_quiet(connection_info: &ServerConnInfo) -> bool {
+    match connection_info.quiet_hours() {
+        Some(quiet_hours) => quiet_hours.contains(TimeOfDay::now()),
+        None => false,
+    }
+}
+
+async fn do_work(
+    probe: &Probe<'_>,
+    servers: Vec<ServerConnInfo>,
+    interval: Duration,
+    mut receiver: UnboundedReceiver<WorkerMessage>,
+    metadata_sender: Option<UnboundedSender<WorkerMessage>>,
+) {
+    let intervals = servers
+        .iter()
+        .map(|server| server.interval().map_or(interval, Duration::from_secs))
+        .collect();
+    let mut schedule = Schedule::new(intervals, Instant::now());
+    let mut health = HashMap::new();
+
+    while !probe.cancellation.is_cancelled() {
+        let due = schedule.due(Instant::now());
+        let active = due
+            .iter()
+            .map(|&index| &servers[index])
+            .filter(|connection_info| {
+                let quiet = is_quiet(connection_info);
+
+                if quiet {
+                    debug!("Skipping server {} in quiet hours", connection_info.host());
+                }
+
+                !quiet
+            });
+
+        update_servers(probe, active, &mut health).await;
+
+        for index in due {
+            schedule.reschedule(index, Instant::now());
         }
 
-        info!("Updating servers finished");
+        let timeout = schedule.timeout(Instant::now(), interval);
 
-        thread::sleep(interval);
+        match time::timeout(timeout, receiver.recv()).await {
+            Ok(Some(WorkerMessage::RefreshAll)) => {
+                info!("Refresh of all servers requested");
+
+                update_servers(probe, &servers, &mut health).await;
+                schedule.reschedule_all(Instant::now());
+                notify_metadata(&metadata_sender);
+            }
+            Ok(Some(WorkerMessage::RefreshServer(server_name))) => {
+                info!("Refresh of server {} requested", server_name);
+
+                let matching = servers
+                    .iter()
+                    .filter(|connection_info| connection_info.host() == &server_name);
+
+                update_servers(probe, matching, &mut health).await;
+
+                for (index, connection_info) in servers.iter().enumerate() {
+                    if connection_info.host() == &server_name {
+                        schedule.reschedule(index, Instant::now());
+                    }
+                }
+
+                notify_metadata(&metadata_sender);
+            }
+            Ok(Some(WorkerMessage::Shutdown)) | Ok(None) => break,
+            Err(_) => {}
+        }
     }
+
+    info!("Database worker stopped");
 }
 
 impl DatabaseWorker {
     pub fn spawn(
         servers: Vec<ServerConnInfo>,
         interval: u64,
+        probe_timeout: u64,
         state: State,
-    ) -> IoResult<DatabaseWorker> {
+        connector: Arc<dyn Connector>,
+        metadata_sender: Option<UnboundedSender<WorkerMessage>>,
+    ) -> DatabaseWorker {
         let interval = Duration::from_secs(interval);
-        let join_handle = Builder::new()
-            .name("Database worker".into())
-            .spawn(move || do_work(servers, interval, state))?;
+        let probe_timeout = Duration::from_secs(probe_timeout);
+        let task = WorkerTask::spawn("Database worker", move |receiver, cancellation| async move {
+            let probe = Probe {
+                connector: &*connector,
+                state: &state,
+                timeout: probe_timeout,
+                cancellation: &cancellation,
+            };
+
+            do_work(&probe, servers, interval, receiver, metadata_sender).await
+        });
+
+        DatabaseWorker { task }
+    }
 
-        Ok(DatabaseWorker {
-            join_handle: join_handle,
-        })
+    pub fn sender(&self) -> UnboundedSender<WorkerMessage> {
+        self.task.sender()
     }
 
-    pub fn join(self) {
-        if let Err(_) = self.join_handle.join() {
-            info!("Failed to join database worker thread");
-        }
+    pub fn shutdown(&self) {
+        self.task.shutdown();
+    }
+
+    pub async fn join_timeout(self, timeout: Duration) -> bool {
+        self.task.join_timeout(timeout).await
     }
 }
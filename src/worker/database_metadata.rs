@@ -0,0 +1,53 @@
+#[derive(Debug, Clone)]
+pub struct DatabaseMetadata {
+    server_name: String,
+    database_name: String,
+    commit: i64,
+    branch_name: String,
+    project_name: String,
+    created: Option<i64>,
+}
+
+impl DatabaseMetadata {
+    pub fn new(
+        server_name: &str,
+        database_name: &str,
+        commit: i64,
+        branch_name: &str,
+        project_name: &str,
+        created: Option<i64>,
+    ) -> DatabaseMetadata {
+        DatabaseMetadata {
+            server_name: server_name.into(),
+            database_name: database_name.into(),
+            commit,
+            branch_name: branch_name.into(),
+            project_name: project_name.into(),
+            created,
+        }
+    }
+
+    pub fn server_name(&self) -> &String {
+        &self.server_name
+    }
+
+    pub fn database_name(&self) -> &String {
+        &self.database_name
+    }
+
+    pub fn commit(&self) -> i64 {
+        self.commit
+    }
+
+    pub fn branch_name(&self) -> &String {
+        &self.branch_name
+    }
+
+    pub fn project_name(&self) -> &String {
+        &self.project_name
+    }
+
+    pub fn created(&self) -> Option<i64> {
+        self.created
+    }
+}
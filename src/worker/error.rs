@@ -2,8 +2,11 @@ use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FtmResult;
+use std::io::Error as IoError;
 
-use postgres::Error as PgError;
+use mysql::Error as MysqlError;
+use tokio::task::JoinError;
+use tokio_postgres::Error as PgError;
 
 pub type WorkerResult<T> = Result<T, WorkerError>;
 
@@ -38,32 +41,56 @@ impl Error for WorkerError {
             WorkerError::DatabaseError { .. } => "Database error",
             WorkerError::IoError { .. } => "IO error",
             WorkerError::ConversionError { .. } => "Conversion error",
-            WorkerError::UnknownError { .. } => "Unknown error",
+            WorkerError::UnknownError => "Unknown error",
         }
     }
 }
 
 impl From<PgError> for WorkerError {
     fn from(error: PgError) -> WorkerError {
-        if let Some(err) = error.as_connection() {
-            WorkerError::ConnectError {
-                message: err.description().into(),
-            }
-        } else if let Some(err) = error.as_db() {
+        if let Some(err) = error.as_db_error() {
             WorkerError::DatabaseError {
-                code: err.code.code().into(),
-                message: err.message.clone(),
-            }
-        } else if let Some(err) = error.as_conversion() {
-            WorkerError::ConversionError {
-                message: err.description().into(),
+                code: err.code().code().into(),
+                message: err.message().into(),
             }
-        } else if let Some(err) = error.as_io() {
+        } else if let Some(err) = error.source().and_then(|err| err.downcast_ref::<IoError>()) {
             WorkerError::IoError {
-                message: err.description().into(),
+                message: format!("{}", err),
             }
         } else {
-            WorkerError::UnknownError
+            WorkerError::ConnectError {
+                message: format!("{}", error),
+            }
+        }
+    }
+}
+
+impl From<MysqlError> for WorkerError {
+    fn from(error: MysqlError) -> WorkerError {
+        match error {
+            MysqlError::MySqlError(ref err) => WorkerError::DatabaseError {
+                code: err.state.clone(),
+                message: err.message.clone(),
+            },
+            MysqlError::IoError(ref err) => WorkerError::IoError {
+                message: format!("{}", err),
+            },
+            MysqlError::FromValueError(_) | MysqlError::FromRowError(_) => {
+                WorkerError::ConversionError {
+                    message: format!("{}", error),
+                }
+            }
+            _ => WorkerError::ConnectError {
+                message: format!("{}", error),
+            },
+        }
+    }
+}
+
+impl From<JoinError> for WorkerError {
+    fn from(error: JoinError) -> WorkerError {
+        WorkerError::IoError {
+            message: format!("{}", error),
         }
     }
 }
@@ -1,183 +1,137 @@
 use std::collections::HashSet;
-use std::io::Result as IoResult;
-use std::thread::Builder;
-use std::thread::JoinHandle;
-use std::thread;
+use std::sync::Arc;
 use std::time::Duration;
 
-use postgres::Connection;
-use postgres::TlsMode;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::time;
+use tokio_postgres::Statement;
 
-use config::MetadataConnInfo;
-use state::State;
+use database_monitor_api::Event;
 
+use crate::config::MetadataConnInfo;
+use crate::state::DatabaseInfo;
+use crate::state::State;
+
+use super::connect_postgres;
+use super::Cancellation;
+use super::Connector;
+use super::ServerDatabase;
+use super::WorkerMessage;
 use super::WorkerResult;
+use super::WorkerTask;
 
 pub struct MetadataWorker {
-    join_handle: JoinHandle<()>,
+    task: WorkerTask,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct ServerDatabase {
-    server_name: String,
-    database_name: String,
-}
+async fn update_database_info(
+    connector: &dyn Connector,
+    connection_info: &MetadataConnInfo,
+    pending_databases: &[ServerDatabase],
+    state: &State,
+) -> WorkerResult<()> {
+    let databases = connector
+        .query_metadata(connection_info, pending_databases)
+        .await?;
 
-impl ServerDatabase {
-    fn new(server_name: &str, database_name: &str) -> ServerDatabase {
-        ServerDatabase {
-            server_name: server_name.into(),
-            database_name: database_name.into(),
-        }
-    }
+    state.set_databases_metadata(&databases);
 
-    fn server_name(&self) -> &String {
-        &self.server_name
+    if !databases.is_empty() {
+        state.publish(Event::refresh_finished(state.last_update()));
     }
 
-    fn database_name(&self) -> &String {
-        &self.database_name
-    }
+    Ok(())
 }
 
-#[derive(Debug)]
-struct DatabaseMatadata {
-    server_name: String,
-    database_name: String,
-    commit: i64,
-    branch_name: String,
-    project_name: String,
-}
+pub async fn prepare_metadata_query(
+    connection_info: &MetadataConnInfo,
+) -> WorkerResult<Statement> {
+    let client = connect_postgres(
+        connection_info.host(),
+        connection_info.port(),
+        connection_info.role(),
+        connection_info.password(),
+        connection_info.database(),
+    )
+    .await?;
 
-impl DatabaseMatadata {
-    fn new(
-        server_name: &str,
-        database_name: &str,
-        commit: i64,
-        branch_name: &str,
-        project_name: &str,
-    ) -> DatabaseMatadata {
-        DatabaseMatadata {
-            server_name: server_name.into(),
-            database_name: database_name.into(),
-            commit,
-            branch_name: branch_name.into(),
-            project_name: project_name.into(),
-        }
-    }
+    Ok(client.prepare(connection_info.query()).await?)
+}
 
-    fn server_name(&self) -> &String {
-        &self.server_name
-    }
+fn has_metadata(database: &DatabaseInfo) -> bool {
+    database.commit().is_some()
+        || database.project_name().is_some()
+        || database.branch_name().is_some()
+}
 
-    fn database_name(&self) -> &String {
-        &self.database_name
-    }
+pub async fn update_metadata(
+    connector: &dyn Connector,
+    connection_info: &MetadataConnInfo,
+    state: &State,
+    ignored_databases: &mut HashSet<ServerDatabase>,
+) -> WorkerResult<()> {
+    let mut pending_databases = Vec::new();
 
-    fn commit(&self) -> i64 {
-        self.commit
-    }
+    state.for_each_database(&mut |_, database| {
+        if !has_metadata(database) {
+            let server_database =
+                ServerDatabase::new(database.server_name(), database.database_name());
 
-    fn branch_name(&self) -> &String {
-        &self.branch_name
-    }
+            if !ignored_databases.contains(&server_database) {
+                pending_databases.push(server_database);
+            }
+        }
+    });
 
-    fn project_name(&self) -> &String {
-        &self.project_name
+    if pending_databases.is_empty() {
+        return Ok(());
     }
-}
 
-fn query_database_metadata(
-    connection_info: &MetadataConnInfo,
-    databases: &Vec<ServerDatabase>,
-) -> WorkerResult<Vec<DatabaseMatadata>> {
-    let url = format!(
-        "postgresql://{3}:{4}@{0}:{1}/{2}",
-        connection_info.host(),
-        connection_info.port(),
-        connection_info.database(),
-        connection_info.role(),
-        connection_info.password()
-    );
-    let connection = Connection::connect(url, TlsMode::None)?;
-    let statement = connection.prepare(connection_info.query())?;
-    let mut result = Vec::default();
-
-    for database in databases {
-        let server_name = database.server_name();
-        let database_name = database.database_name();
-
-        debug!("Updating database {}/{}", server_name, database_name);
-
-        for row in &statement.query(&[server_name, database_name])? {
-            let commit: i64 = row.get(0);
-            let branch_name: String = row.get(1);
-            let project_name: String = row.get(2);
-
-            result.push(DatabaseMatadata::new(
-                server_name,
-                database_name,
-                commit,
-                &branch_name,
-                &project_name,
-            ));
-        }
-    }
+    update_database_info(connector, connection_info, &pending_databases, state).await?;
+    ignored_databases.extend(pending_databases);
 
-    Ok(result)
+    Ok(())
 }
 
-fn update_database_info(
+async fn do_work(
+    connector: Arc<dyn Connector>,
     connection_info: &MetadataConnInfo,
-    pending_databases: &Vec<ServerDatabase>,
-    state: &State,
+    interval: Duration,
+    state: State,
+    mut receiver: UnboundedReceiver<WorkerMessage>,
+    cancellation: Cancellation,
 ) {
-    match query_database_metadata(connection_info, &pending_databases) {
-        Ok(databases) => for database in &databases {
-            state.set_database_metadata(
-                database.server_name(),
-                database.database_name(),
-                database.commit(),
-                database.branch_name(),
-                database.project_name(),
-            );
-        },
-        Err(err) => {
-            warn!("Failed to update metadata: {}", err);
-        }
-    };
-}
-
-fn do_work(connection_info: &MetadataConnInfo, interval: Duration, state: State) {
     let mut ignored_databases: HashSet<ServerDatabase> = HashSet::default();
 
-    loop {
+    while !cancellation.is_cancelled() {
         info!("Updating metadata started");
 
-        let mut pending_databases = Vec::new();
+        let update = update_metadata(&*connector, connection_info, &state, &mut ignored_databases);
 
-        state.for_each_database(&mut |_, database| {
-            if !database.commit().is_some() && !database.project_name().is_some()
-                && !database.branch_name().is_some()
-            {
-                let server_database =
-                    ServerDatabase::new(database.server_name(), database.database_name());
-
-                if !ignored_databases.contains(&server_database) {
-                    ignored_databases.insert(server_database.clone());
-                    pending_databases.push(server_database);
+        tokio::select! {
+            result = update => {
+                if let Err(err) = result {
+                    warn!("Failed to update metadata: {}", err);
                 }
             }
-        });
+            _ = cancellation.cancelled() => {
+                info!("Metadata update cancelled by shutdown");
 
-        if !pending_databases.is_empty() {
-            update_database_info(connection_info, &pending_databases, &state);
+                break;
+            }
         }
 
         info!("Updating metadata finished");
 
-        thread::sleep(interval);
+        match time::timeout(interval, receiver.recv()).await {
+            Ok(Some(WorkerMessage::Shutdown)) | Ok(None) => break,
+            Ok(Some(message)) => debug!("Metadata worker woken up by {:?}", message),
+            Err(_) => {}
+        }
     }
+
+    info!("Metadata worker stopped");
 }
 
 impl MetadataWorker {
@@ -185,20 +139,36 @@ impl MetadataWorker {
         connection_info: MetadataConnInfo,
         interval: u64,
         state: State,
-    ) -> IoResult<MetadataWorker> {
+        connector: Arc<dyn Connector>,
+    ) -> MetadataWorker {
         let interval = Duration::from_secs(interval);
-        let join_handle = Builder::new()
-            .name("Meta-data worker".into())
-            .spawn(move || do_work(&connection_info, interval, state))?;
+        let task = WorkerTask::spawn(
+            "Meta-data worker",
+            move |receiver, cancellation| async move {
+                do_work(
+                    connector,
+                    &connection_info,
+                    interval,
+                    state,
+                    receiver,
+                    cancellation,
+                )
+                .await
+            },
+        );
+
+        MetadataWorker { task }
+    }
 
-        Ok(MetadataWorker {
-            join_handle: join_handle,
-        })
+    pub fn sender(&self) -> UnboundedSender<WorkerMessage> {
+        self.task.sender()
     }
 
-    pub fn join(self) {
-        if let Err(_) = self.join_handle.join() {
-            info!("Failed to join metadata worker thread");
-        }
+    pub fn shutdown(&self) {
+        self.task.shutdown();
+    }
+
+    pub async fn join_timeout(self, timeout: Duration) -> bool {
+        self.task.join_timeout(timeout).await
     }
 }
@@ -1,8 +1,32 @@
+mod cancellation;
+mod connection;
+mod connector;
 mod database;
+mod database_metadata;
 mod error;
+mod message;
 mod metadata;
+mod probe;
+mod schedule;
+mod server_database;
+mod task;
 
-use self::error::WorkerResult;
+use self::cancellation::Cancellation;
+use self::schedule::Schedule;
+use self::task::WorkerTask;
 
+pub use self::connection::connect_postgres;
+pub use self::connector::Connector;
+pub use self::connector::DefaultConnector;
 pub use self::database::DatabaseWorker;
+pub use self::database_metadata::DatabaseMetadata;
+pub use self::error::WorkerError;
+pub use self::error::WorkerResult;
+pub use self::message::WorkerMessage;
+pub use self::metadata::prepare_metadata_query;
+pub use self::metadata::update_metadata;
 pub use self::metadata::MetadataWorker;
+pub use self::probe::probe_for;
+pub use self::probe::probe_server;
+pub use self::probe::Probe;
+pub use self::server_database::ServerDatabase;
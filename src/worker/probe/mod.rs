@@ -0,0 +1,10 @@
+mod mysql;
+mod postgres;
+mod select;
+mod traits;
+
+pub use self::mysql::MysqlProbe;
+pub use self::postgres::PostgresProbe;
+pub use self::select::probe_for;
+pub use self::select::probe_server;
+pub use self::traits::Probe;
@@ -0,0 +1,3 @@
+SELECT
+    CAST(SUM(t.DATA_LENGTH + t.INDEX_LENGTH) AS SIGNED)
+FROM information_schema.TABLES AS t
@@ -0,0 +1,11 @@
+SELECT
+    s.SCHEMA_NAME,
+    s.DEFAULT_COLLATION_NAME,
+    CAST(COUNT(p.ID) AS SIGNED)
+FROM information_schema.SCHEMATA AS s
+    LEFT JOIN information_schema.PROCESSLIST AS p ON ( p.DB = s.SCHEMA_NAME )
+WHERE
+    s.SCHEMA_NAME NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
+GROUP BY
+    s.SCHEMA_NAME,
+    s.DEFAULT_COLLATION_NAME
@@ -0,0 +1,7 @@
+SELECT
+    CONCAT(u.User, '@', u.Host),
+    u.Super_priv = 'Y'
+FROM mysql.user AS u
+ORDER BY
+    u.User,
+    u.Host
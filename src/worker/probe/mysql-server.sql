@@ -0,0 +1,3 @@
+SELECT
+    VERSION(),
+    CAST(@@max_connections AS SIGNED)
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mysql::prelude::FromValue;
+use mysql::prelude::Queryable;
+use mysql::Conn;
+use mysql::OptsBuilder;
+use mysql::Row;
+use tokio::task;
+
+use crate::config::ServerConnInfo;
+use crate::state::DatabaseInfo;
+use crate::state::RoleInfo;
+use crate::state::ServerDetails;
+
+use crate::worker::WorkerError;
+use crate::worker::WorkerResult;
+
+use super::Probe;
+
+const REPLICA_STATUS_QUERIES: &[&str] = &["SHOW REPLICA STATUS", "SHOW SLAVE STATUS"];
+const SOURCE_HOST_COLUMNS: &[&str] = &["Source_Host", "Master_Host"];
+const REPLICA_LAG_COLUMNS: &[&str] =
+    &["Seconds_Behind_Source", "Seconds_Behind_Master"];
+const UNKNOWN_OWNER: &str = "";
+
+fn uptime(conn: &mut Conn) -> WorkerResult<i64> {
+    let status: Option<(String, i64)> = conn.query_first("SHOW GLOBAL STATUS LIKE 'Uptime'")?;
+
+    status
+        .map(|(_, uptime)| uptime)
+        .ok_or(WorkerError::UnknownError)
+}
+
+fn cluster_size(conn: &mut Conn) -> Option<i64> {
+    match conn.query_first::<Option<i64>, _>(include_str!("mysql-cluster-size.sql")) {
+        Ok(size) => size.and_then(|size| size),
+        Err(err) => {
+            debug!("Cluster size is not available: {}", err);
+
+            None
+        }
+    }
+}
+
+fn server_roles(conn: &mut Conn) -> Vec<RoleInfo> {
+    let roles = conn.query_map(
+        include_str!("mysql-roles.sql"),
+        |(name, superuser): (String, bool)| RoleInfo::new(&name, superuser, true),
+    );
+
+    match roles {
+        Ok(roles) => roles,
+        Err(err) => {
+            debug!("Roles are not available: {}", err);
+
+            Vec::new()
+        }
+    }
+}
+
+fn column<T>(row: &Row, columns: &[&str]) -> Option<T>
+where
+    T: FromValue,
+{
+    columns
+        .iter()
+        .filter_map(|&column| row.get::<Option<T>, _>(column))
+        .next()
+        .and_then(|value| value)
+}
+
+fn replica_status(conn: &mut Conn) -> Option<(Option<String>, Option<i64>)> {
+    for query in REPLICA_STATUS_QUERIES {
+        match conn.query_first::<Row, _>(*query) {
+            Ok(row) => {
+                return row.map(|row| {
+                    (
+                        column(&row, SOURCE_HOST_COLUMNS),
+                        column(&row, REPLICA_LAG_COLUMNS),
+                    )
+                })
+            }
+            Err(err) => debug!("{} failed: {}", query, err),
+        }
+    }
+
+    None
+}
+
+fn server_details(
+    connection_info: &ServerConnInfo,
+    conn: &mut Conn,
+) -> WorkerResult<ServerDetails> {
+    let (version, max_connections): (String, i64) = conn
+        .query_first(include_str!("mysql-server.sql"))?
+        .ok_or(WorkerError::UnknownError)?;
+    let start_time = time::get_time().sec - uptime(conn)?;
+    let replica_status = replica_status(conn);
+    let in_recovery = replica_status.is_some();
+    let (upstream, replication_lag) = replica_status.unwrap_or((None, None));
+    let upstream = connection_info.primary().clone().or(upstream);
+    let details = ServerDetails::new(
+        &version,
+        start_time,
+        in_recovery,
+        cluster_size(conn),
+        max_connections,
+        server_roles(conn),
+    );
+
+    Ok(details.with_replication(upstream, replication_lag, Vec::new()))
+}
+
+fn database_infos(
+    connection_info: &ServerConnInfo,
+    conn: &mut Conn,
+) -> WorkerResult<Vec<DatabaseInfo>> {
+    let result = conn.query_map(
+        include_str!("mysql-databases.sql"),
+        |(database_name, collation_name, backends): (String, String, i64)| {
+            DatabaseInfo::new(
+                connection_info.host(),
+                &database_name,
+                &collation_name,
+                UNKNOWN_OWNER,
+                backends,
+            )
+        },
+    )?;
+
+    Ok(result)
+}
+
+fn probe_blocking(
+    connection_info: &ServerConnInfo,
+    timeout: Duration,
+) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)> {
+    let opts = OptsBuilder::new()
+        .ip_or_hostname(Some(connection_info.host().as_str()))
+        .tcp_port(connection_info.port())
+        .user(Some(connection_info.role().as_str()))
+        .pass(Some(connection_info.password().as_str()))
+        .tcp_connect_timeout(Some(timeout))
+        .read_timeout(Some(timeout))
+        .write_timeout(Some(timeout));
+    let mut conn = Conn::new(opts)?;
+    let details = server_details(connection_info, &mut conn)?;
+    let databases = database_infos(connection_info, &mut conn)?;
+
+    Ok((details, databases))
+}
+
+pub struct MysqlProbe;
+
+#[async_trait]
+impl Probe for MysqlProbe {
+    async fn probe(
+        &self,
+        connection_info: &ServerConnInfo,
+        timeout: Duration,
+    ) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)> {
+        let connection_info = connection_info.clone();
+
+        task::spawn_blocking(move || probe_blocking(&connection_info, timeout)).await?
+    }
+}
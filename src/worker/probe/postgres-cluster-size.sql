@@ -0,0 +1,5 @@
+SELECT
+    SUM(pg_database_size(d.oid))::BIGINT
+FROM pg_database AS d
+WHERE
+    d.datallowconn = TRUE
@@ -0,0 +1,4 @@
+SELECT
+    d.datname,
+    EXTRACT(EPOCH FROM (pg_stat_file('base/' || d.oid || '/PG_VERSION', TRUE)).modification)::BIGINT
+FROM pg_database AS d
@@ -1,9 +1,11 @@
 SELECT
     d.datname,
     d.datcollate,
-    r.rolname
+    r.rolname,
+    COALESCE(s.numbackends, 0)::BIGINT
 FROM pg_database AS d
     INNER JOIN pg_roles AS r ON ( r.oid = d.datdba )
+    LEFT JOIN pg_stat_database AS s ON ( s.datid = d.oid )
 WHERE
     rolcreaterole = FALSE AND
     rolcanlogin = TRUE
@@ -0,0 +1,12 @@
+SELECT
+    r.application_name,
+    HOST(r.client_addr),
+    r.client_hostname,
+    r.state,
+    pg_wal_lsn_diff(
+        CASE WHEN pg_is_in_recovery() THEN pg_last_wal_replay_lsn() ELSE pg_current_wal_lsn() END,
+        r.replay_lsn
+    )::BIGINT
+FROM pg_stat_replication AS r
+ORDER BY
+    r.application_name
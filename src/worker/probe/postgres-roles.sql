@@ -0,0 +1,9 @@
+SELECT
+    r.rolname,
+    r.rolsuper,
+    r.rolcanlogin
+FROM pg_roles AS r
+WHERE
+    r.rolname !~ '^pg_'
+ORDER BY
+    r.rolname
@@ -0,0 +1,8 @@
+SELECT
+    current_setting('server_version'),
+    EXTRACT(EPOCH FROM pg_postmaster_start_time())::BIGINT,
+    pg_is_in_recovery(),
+    current_setting('max_connections')::BIGINT,
+    CASE WHEN pg_is_in_recovery() THEN
+        EXTRACT(EPOCH FROM (now() - pg_last_xact_replay_timestamp()))::BIGINT
+    END
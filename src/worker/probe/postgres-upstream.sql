@@ -0,0 +1,3 @@
+SELECT
+    w.conninfo
+FROM pg_stat_wal_receiver AS w
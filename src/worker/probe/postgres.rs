@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio_postgres::Client;
+
+use crate::config::ServerConnInfo;
+use crate::state::DatabaseInfo;
+use crate::state::ReplicaInfo;
+use crate::state::RoleInfo;
+use crate::state::ServerDetails;
+
+use crate::worker::connect_postgres;
+use crate::worker::WorkerError;
+use crate::worker::WorkerResult;
+
+use super::Probe;
+
+async fn database_created_times(conn: &Client) -> HashMap<String, Option<i64>> {
+    match conn.query(include_str!("postgres-created.sql"), &[]).await {
+        Ok(rows) => rows.iter().map(|row| (row.get(0), row.get(1))).collect(),
+        Err(err) => {
+            debug!("Database creation time is not available: {}", err);
+
+            HashMap::new()
+        }
+    }
+}
+
+async fn cluster_size(conn: &Client) -> Option<i64> {
+    match conn
+        .query(include_str!("postgres-cluster-size.sql"), &[])
+        .await
+    {
+        Ok(rows) => rows.first().and_then(|row| row.get(0)),
+        Err(err) => {
+            debug!("Cluster size is not available: {}", err);
+
+            None
+        }
+    }
+}
+
+async fn server_roles(conn: &Client) -> WorkerResult<Vec<RoleInfo>> {
+    let rows = conn.query(include_str!("postgres-roles.sql"), &[]).await?;
+
+    let result = rows
+        .iter()
+        .map(|row| {
+            let name: String = row.get(0);
+
+            RoleInfo::new(&name, row.get(1), row.get(2))
+        })
+        .collect();
+
+    Ok(result)
+}
+
+async fn server_replicas(conn: &Client) -> Vec<ReplicaInfo> {
+    match conn.query(include_str!("postgres-replicas.sql"), &[]).await {
+        Ok(rows) => rows
+            .iter()
+            .filter_map(|row| {
+                let application_name: Option<String> = row.get(0);
+                let state: Option<String> = row.get(3);
+
+                match (application_name, state) {
+                    (Some(application_name), Some(state)) => Some(ReplicaInfo::new(
+                        &application_name,
+                        &row.get(1),
+                        &row.get(2),
+                        &state,
+                        row.get(4),
+                    )),
+                    _ => {
+                        debug!("Replica details are not visible to the monitoring role");
+
+                        None
+                    }
+                }
+            })
+            .collect(),
+        Err(err) => {
+            debug!("Replicas are not available: {}", err);
+
+            Vec::new()
+        }
+    }
+}
+
+fn conninfo_host(conninfo: &str) -> Option<String> {
+    conninfo
+        .split_whitespace()
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+
+            match (parts.next(), parts.next()) {
+                (Some("host"), Some(host)) => Some(host.trim_matches('\'').to_string()),
+                _ => None,
+            }
+        })
+        .next()
+}
+
+async fn server_upstream(conn: &Client) -> Option<String> {
+    match conn.query(include_str!("postgres-upstream.sql"), &[]).await {
+        Ok(rows) => rows.first().and_then(|row| {
+            let conninfo: Option<String> = row.get(0);
+
+            conninfo.and_then(|conninfo| conninfo_host(&conninfo))
+        }),
+        Err(err) => {
+            debug!("Upstream server is not available: {}", err);
+
+            None
+        }
+    }
+}
+
+async fn server_details(
+    connection_info: &ServerConnInfo,
+    conn: &Client,
+) -> WorkerResult<ServerDetails> {
+    let rows = conn.query(include_str!("postgres-server.sql"), &[]).await?;
+    let row = rows.first().ok_or(WorkerError::UnknownError)?;
+    let version: String = row.get(0);
+    let in_recovery: bool = row.get(2);
+    let upstream = match connection_info.primary() {
+        Some(primary) => Some(primary.clone()),
+        None if in_recovery => server_upstream(conn).await,
+        None => None,
+    };
+    let details = ServerDetails::new(
+        &version,
+        row.get(1),
+        in_recovery,
+        cluster_size(conn).await,
+        row.get(3),
+        server_roles(conn).await?,
+    );
+
+    Ok(details.with_replication(upstream, row.get(4), server_replicas(conn).await))
+}
+
+async fn database_infos(
+    connection_info: &ServerConnInfo,
+    conn: &Client,
+) -> WorkerResult<Vec<DatabaseInfo>> {
+    let rows = conn
+        .query(include_str!("postgres-databases.sql"), &[])
+        .await?;
+    let created_times = database_created_times(conn).await;
+
+    let result = rows
+        .into_iter()
+        .map(|row| {
+            let database_name: String = row.get(0);
+            let collation_name: String = row.get(1);
+            let owner: String = row.get(2);
+            let backends: i64 = row.get(3);
+            let mut database_info = DatabaseInfo::new(
+                connection_info.host(),
+                &database_name,
+                &collation_name,
+                &owner,
+                backends,
+            );
+
+            if let Some(&created) = created_times.get(&database_name) {
+                database_info.set_server_created(created);
+            }
+
+            database_info
+        })
+        .collect();
+
+    Ok(result)
+}
+
+pub struct PostgresProbe;
+
+#[async_trait]
+impl Probe for PostgresProbe {
+    async fn probe(
+        &self,
+        connection_info: &ServerConnInfo,
+        _timeout: Duration,
+    ) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)> {
+        let conn = connect_postgres(
+            connection_info.host(),
+            connection_info.port(),
+            connection_info.role(),
+            connection_info.password(),
+            "postgres",
+        )
+        .await?;
+        let details = server_details(connection_info, &conn).await?;
+        let databases = database_infos(connection_info, &conn).await?;
+
+        Ok((details, databases))
+    }
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use crate::config::ServerConnInfo;
+use crate::config::ServerKind;
+use crate::state::DatabaseInfo;
+use crate::state::ServerDetails;
+
+use crate::worker::WorkerResult;
+
+use super::MysqlProbe;
+use super::PostgresProbe;
+use super::Probe;
+
+pub fn probe_for(kind: ServerKind) -> Box<dyn Probe> {
+    match kind {
+        ServerKind::Postgres => Box::new(PostgresProbe),
+        ServerKind::Mysql => Box::new(MysqlProbe),
+    }
+}
+
+pub async fn probe_server(
+    connection_info: &ServerConnInfo,
+    timeout: Duration,
+) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)> {
+    probe_for(connection_info.kind())
+        .probe(connection_info, timeout)
+        .await
+}
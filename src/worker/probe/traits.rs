@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::config::ServerConnInfo;
+use crate::state::DatabaseInfo;
+use crate::state::ServerDetails;
+
+use crate::worker::WorkerResult;
+
+#[async_trait]
+pub trait Probe: Send + Sync {
+    async fn probe(
+        &self,
+        connection_info: &ServerConnInfo,
+        timeout: Duration,
+    ) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)>;
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+use std::time::Instant;
+
+#[derive(Debug)]
+pub struct Schedule {
+    intervals: Vec<Duration>,
+    next_due: Vec<Instant>,
+}
+
+impl Schedule {
+    pub fn new(intervals: Vec<Duration>, now: Instant) -> Schedule {
+        let next_due = intervals.iter().map(|_| now).collect();
+
+        Schedule {
+            intervals,
+            next_due,
+        }
+    }
+
+    pub fn due(&self, now: Instant) -> Vec<usize> {
+        let mut due: Vec<usize> = (0..self.next_due.len())
+            .filter(|&index| self.next_due[index] <= now)
+            .collect();
+
+        due.sort_by_key(|&index| self.next_due[index]);
+        due
+    }
+
+    pub fn reschedule(&mut self, index: usize, now: Instant) {
+        self.next_due[index] = now + self.intervals[index];
+    }
+
+    pub fn reschedule_all(&mut self, now: Instant) {
+        for index in 0..self.next_due.len() {
+            self.reschedule(index, now);
+        }
+    }
+
+    pub fn timeout(&self, now: Instant, default: Duration) -> Duration {
+        match self.next_due.iter().min() {
+            Some(&next_due) if next_due > now => next_due - now,
+            Some(_) => Duration::from_secs(0),
+            None => default,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct ServerDatabase {
+    server_name: String,
+    database_name: String,
+}
+
+impl ServerDatabase {
+    pub fn new(server_name: &str, database_name: &str) -> ServerDatabase {
+        ServerDatabase {
+            server_name: server_name.into(),
+            database_name: database_name.into(),
+        }
+    }
+
+    pub fn server_name(&self) -> &String {
+        &self.server_name
+    }
+
+    pub fn database_name(&self) -> &String {
+        &self.database_name
+    }
+}
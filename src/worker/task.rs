@@ -0,0 +1,65 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+use super::Cancellation;
+use super::WorkerMessage;
+
+pub struct WorkerTask {
+    name: String,
+    join_handle: JoinHandle<()>,
+    sender: UnboundedSender<WorkerMessage>,
+    cancellation: Cancellation,
+}
+
+impl WorkerTask {
+    pub fn spawn<F, T>(name: &str, work: F) -> WorkerTask
+    where
+        F: FnOnce(UnboundedReceiver<WorkerMessage>, Cancellation) -> T,
+        T: Future<Output = ()> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let cancellation = Cancellation::default();
+        let join_handle = tokio::spawn(work(receiver, cancellation.clone()));
+
+        WorkerTask {
+            name: name.into(),
+            join_handle,
+            sender,
+            cancellation,
+        }
+    }
+
+    pub fn sender(&self) -> UnboundedSender<WorkerMessage> {
+        self.sender.clone()
+    }
+
+    pub fn shutdown(&self) {
+        self.cancellation.cancel();
+
+        if let Err(err) = self.sender.send(WorkerMessage::Shutdown) {
+            warn!("Failed to send shutdown to {}: {}", self.name, err);
+        }
+    }
+
+    pub async fn join_timeout(self, timeout: Duration) -> bool {
+        match time::timeout(timeout, self.join_handle).await {
+            Ok(Ok(())) => true,
+            Ok(Err(err)) => {
+                info!("Failed to join {} task: {}", self.name, err);
+
+                true
+            }
+            Err(_) => {
+                warn!("The {} task did not stop in {:?}", self.name, timeout);
+
+                false
+            }
+        }
+    }
+}
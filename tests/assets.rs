@@ -0,0 +1,83 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+
+use database_monitor::assets::Assets;
+
+use common::free_port;
+use common::http_get;
+use common::project_path;
+use common::wait_for_port;
+use common::Monitor;
+
+fn read(path: &str) -> Vec<u8> {
+    fs::read(project_path(path)).unwrap()
+}
+
+#[test]
+fn embedded_assets_match_sources() {
+    let assets = Assets::default();
+
+    for path in &[
+        "template/index.html",
+        "template/servers.html",
+        "public/js/search-database.js",
+        "public/images/logo.png",
+    ] {
+        assert_eq!(assets.get(path).as_deref(), Some(read(path).as_slice()));
+    }
+
+    assert!(assets.get("public/missing.js").is_none());
+    assert!(assets.get("Cargo.toml").is_none());
+}
+
+#[test]
+fn assets_directory_overrides_embedded_copies() {
+    let directory = env::temp_dir().join(format!("database-monitor-assets-{}", process::id()));
+
+    fs::create_dir_all(directory.join("template")).unwrap();
+    fs::write(directory.join("template/index.html"), "override").unwrap();
+
+    let assets = Assets::new(Some(directory.clone()));
+    let manifest = project_path("Cargo.toml");
+
+    assert_eq!(
+        assets.get("template/index.html").as_deref(),
+        Some(&b"override"[..])
+    );
+    assert!(assets.get("template/servers.html").is_none());
+    assert!(assets.get("../Cargo.toml").is_none());
+    assert!(assets.get(&manifest.display().to_string()).is_none());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn serves_embedded_assets_outside_of_repository() {
+    let port = free_port();
+    let config = format!(
+        r#"{{ "address": "127.0.0.1", "port": {}, "interval": 600, "servers": [] }}"#,
+        port
+    );
+    let _monitor = Monitor::start("assets", &config);
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    let (status, body) = http_get(port, "/");
+    let template = String::from_utf8(read("template/index.html")).unwrap();
+
+    assert_eq!(status, 200);
+    assert_eq!(body, template.replace("{{base_path}}", ""));
+
+    let (status, body) = http_get(port, "/public/js/servers.js");
+
+    assert_eq!(status, 200);
+    assert_eq!(body.as_bytes(), read("public/js/servers.js").as_slice());
+
+    let (status, _) = http_get(port, "/public/js/missing.js");
+
+    assert_eq!(status, 404);
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use sha1::Digest;
+use sha1::Sha1;
+
+use common::free_port;
+use common::http_request;
+use common::wait_for_port;
+use common::Monitor;
+
+const SERVERS: &str = r#"[
+    { "host": "127.0.0.2", "port": 1, "role": "user", "password": "password" },
+    { "host": "127.0.0.3", "port": 1, "role": "user", "password": "password" }
+]"#;
+
+fn start(name: &str, auth: &str) -> (Monitor, u16) {
+    start_with_proxies(name, auth, "[]")
+}
+
+fn start_with_proxies(name: &str, auth: &str, trusted_proxies: &str) -> (Monitor, u16) {
+    let port = free_port();
+    let config = format!(
+        r#"{{
+            "address": "127.0.0.1",
+            "port": {},
+            "interval": 600,
+            "servers": {},
+            "auth": {},
+            "trusted_proxies": {}
+        }}"#,
+        port, SERVERS, auth, trusted_proxies
+    );
+    let monitor = Monitor::start(name, &config);
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    (monitor, port)
+}
+
+fn write_htpasswd(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!(
+        "database-monitor-{}-{}.htpasswd",
+        name,
+        std::process::id()
+    ));
+    let bcrypt = bcrypt::hash("password", 4).unwrap();
+    let sha = base64::encode(Sha1::digest(b"password"));
+
+    fs::write(
+        &path,
+        format!("# users\nalice:{}\nlegacy:{{SHA}}{}\ninvalid line\n", bcrypt, sha),
+    ).unwrap();
+
+    path
+}
+
+fn basic(user: &str, password: &str) -> String {
+    format!("Basic {}", base64::encode(format!("{}:{}", user, password)))
+}
+
+fn get_status(port: u16, headers: &[(&str, &str)]) -> u16 {
+    http_request(port, "GET", "/api/v1/status", headers).0
+}
+
+fn refresh(port: u16, token: &str, server_name: &str) -> (u16, String) {
+    let authorization = format!("Bearer {}", token);
+
+    http_request(
+        port,
+        "POST",
+        &format!("/api/v1/servers/{}/refresh", server_name),
+        &[("Authorization", &authorization)],
+    )
+}
+
+#[test]
+fn tokens_and_htpasswd_are_verified() {
+    let htpasswd = write_htpasswd("auth-credentials");
+    let auth = format!(
+        r#"{{
+            "tokens": [ {{ "user": "ci", "token": "secret" }} ],
+            "htpasswd": "{}",
+            "default_visibility": "all"
+        }}"#,
+        htpasswd.display()
+    );
+    let (_monitor, port) = start("auth-credentials", &auth);
+
+    assert_eq!(get_status(port, &[]), 401);
+    assert_eq!(get_status(port, &[("Authorization", "Bearer secret")]), 200);
+    assert_eq!(get_status(port, &[("Authorization", "Bearer  secret ")]), 200);
+    assert_eq!(get_status(port, &[("Authorization", "Bearer secre")]), 401);
+    assert_eq!(get_status(port, &[("Authorization", "Bearer secrets")]), 401);
+    assert_eq!(get_status(port, &[("Authorization", "Token secret")]), 401);
+    assert_eq!(get_status(port, &[("Authorization", &basic("alice", "password"))]), 200);
+    assert_eq!(get_status(port, &[("Authorization", &basic("alice", "wrong"))]), 401);
+    assert_eq!(get_status(port, &[("Authorization", &basic("legacy", "password"))]), 200);
+    assert_eq!(get_status(port, &[("Authorization", &basic("legacy", "wrong"))]), 401);
+    assert_eq!(get_status(port, &[("Authorization", &basic("nobody", "password"))]), 401);
+    assert_eq!(get_status(port, &[("Authorization", "Basic not-base64!")]), 401);
+
+    let _ = fs::remove_file(htpasswd);
+}
+
+#[test]
+fn proxy_header_is_used_from_trusted_addresses_only() {
+    let auth = r#"{
+        "proxy": { "header": "X-Remote-User" },
+        "default_visibility": "all"
+    }"#;
+    let (_trusted_monitor, trusted_port) =
+        start_with_proxies("auth-proxy-trusted", auth, r#"[ "127.0.0.1" ]"#);
+    let (_untrusted_monitor, untrusted_port) =
+        start_with_proxies("auth-proxy-untrusted", auth, r#"[ "10.0.0.1" ]"#);
+
+    assert_eq!(get_status(trusted_port, &[("X-Remote-User", "alice")]), 200);
+    assert_eq!(get_status(trusted_port, &[("X-Remote-User", " ")]), 401);
+    assert_eq!(get_status(trusted_port, &[]), 401);
+    assert_eq!(get_status(untrusted_port, &[("X-Remote-User", "alice")]), 401);
+}
+
+#[test]
+fn visibility_and_permissions_are_enforced() {
+    let auth = r#"{
+        "tokens": [
+            { "user": "ci", "token": "ci-token" },
+            { "user": "viewer", "token": "viewer-token" },
+            { "user": "reader", "token": "reader-token" },
+            { "user": "ops", "token": "ops-token" }
+        ],
+        "visibility": [
+            { "user": "ci", "servers": [ "127.0.0.2" ] },
+            { "user": "reader", "servers": [ "127.0.0.2", "127.0.0.3" ] },
+            { "user": "ops", "servers": [ "127.0.0.2", "127.0.0.3" ] }
+        ],
+        "permissions": [
+            { "user": "reader", "permission": "read" },
+            { "user": "ops", "permission": "admin" }
+        ],
+        "default_permission": "refresh"
+    }"#;
+    let (_monitor, port) = start("auth-visibility", auth);
+
+    assert_eq!(refresh(port, "viewer-token", "127.0.0.2").0, 404);
+    assert_eq!(refresh(port, "ci-token", "127.0.0.3").0, 404);
+    assert_eq!(refresh(port, "reader-token", "127.0.0.2").0, 403);
+    assert_eq!(refresh(port, "ci-token", "127.0.0.2").0, 202);
+    assert_eq!(refresh(port, "ops-token", "127.0.0.3").0, 202);
+
+    let drop = |token: &str| {
+        let authorization = format!("Bearer {}", token);
+
+        http_request(
+            port,
+            "DELETE",
+            "/api/v1/servers/127.0.0.2/databases/app?dry_run=true",
+            &[("Authorization", &authorization)],
+        )
+    };
+    let (status, body) = drop("ci-token");
+
+    assert_eq!(status, 403);
+    assert!(body.contains("Management is not permitted"), "{}", body);
+
+    let (status, body) = drop("ops-token");
+
+    assert_eq!(status, 403);
+    assert!(body.contains("Management mode is disabled"), "{}", body);
+}
+
+#[test]
+fn users_without_rules_can_see_all_servers_when_configured() {
+    let auth = r#"{
+        "tokens": [ { "user": "viewer", "token": "viewer-token" } ],
+        "default_visibility": "all",
+        "default_permission": "refresh"
+    }"#;
+    let (_monitor, port) = start("auth-default-visibility", auth);
+
+    assert_eq!(refresh(port, "viewer-token", "127.0.0.2").0, 202);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::time::Duration;
+
+use database_monitor::config::BasePath;
+
+use common::free_port;
+use common::http_get;
+use common::http_get_with_headers;
+use common::wait_for_port;
+use common::Monitor;
+
+fn start(name: &str, base_path: &str, fields: &str) -> (Monitor, u16) {
+    let port = free_port();
+    let config = format!(
+        r#"{{
+            "address": "127.0.0.1",
+            "port": {},
+            "interval": 600,
+            "servers": [],
+            "base_path": "{}"{}
+        }}"#,
+        port, base_path, fields
+    );
+    let monitor = Monitor::start(name, &config);
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    (monitor, port)
+}
+
+#[test]
+fn base_path_is_normalized() {
+    let parse = |value: &str| value.parse::<BasePath>().map(|path| path.as_str().to_string());
+
+    assert_eq!(parse(""), Ok("".to_string()));
+    assert_eq!(parse("/"), Ok("".to_string()));
+    assert_eq!(parse("dbmon"), Ok("/dbmon".to_string()));
+    assert_eq!(parse("/tools/dbmon/"), Ok("/tools/dbmon".to_string()));
+    assert!(parse("/tools//dbmon").is_err());
+    assert!(parse("/tools/../dbmon").is_err());
+    assert!(parse("/{name}").is_err());
+    assert!(parse("/\"><script>").is_err());
+}
+
+#[test]
+fn routes_are_prefixed_with_base_path() {
+    let (_monitor, port) = start("base-path", "/dbmon/", "");
+
+    for path in &["/dbmon", "/dbmon/"] {
+        let (status, body) = http_get(port, path);
+
+        assert_eq!(status, 200);
+        assert!(body.contains(r#"<meta name="base-path" content="/dbmon">"#));
+        assert!(body.contains(r#"data-main="/dbmon/public/js/search-database.js""#));
+    }
+
+    let (status, body) = http_get(port, "/dbmon/servers");
+
+    assert_eq!(status, 200);
+    assert!(body.contains(r#"href="/dbmon/servers""#));
+    assert_eq!(http_get(port, "/dbmon/api/v1/status").0, 200);
+    assert_eq!(http_get(port, "/dbmon/public/js/servers.js").0, 200);
+    assert_eq!(http_get(port, "/api/v1/status").0, 404);
+    assert_eq!(http_get(port, "/").0, 404);
+}
+
+fn trusted_proxies(address: &str) -> String {
+    format!(r#", "trusted_proxies": [ "{}" ]"#, address)
+}
+
+#[test]
+fn forwarded_prefix_is_used_in_links() {
+    let (_monitor, port) = start("forwarded-prefix", "", &trusted_proxies("127.0.0.1"));
+    let get = |prefix: &str| http_get_with_headers(port, "/", &[("X-Forwarded-Prefix", prefix)]);
+
+    let (status, body) = get("/tools/dbmon/");
+
+    assert_eq!(status, 200);
+    assert!(body.contains(r#"<meta name="base-path" content="/tools/dbmon">"#));
+    assert!(body.contains(r#"href="/tools/dbmon/servers""#));
+
+    let (status, body) = get("/\"><script>");
+
+    assert_eq!(status, 200);
+    assert!(body.contains(r#"<meta name="base-path" content="">"#));
+    assert!(!body.contains("<script>"));
+}
+
+#[test]
+fn forwarded_prefix_of_untrusted_client_is_ignored() {
+    let fields = trusted_proxies("127.0.0.2");
+    let (_monitor, port) = start("forwarded-untrusted", "/dbmon", &fields);
+    let (status, body) = http_get_with_headers(port, "/dbmon/", &[("X-Forwarded-Prefix", "/evil")]);
+
+    assert_eq!(status, 200);
+    assert!(body.contains(r#"<meta name="base-path" content="/dbmon">"#));
+    assert!(!body.contains("/evil"));
+}
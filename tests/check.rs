@@ -0,0 +1,245 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::process;
+
+use database_monitor::check::check_configuration;
+use database_monitor::check::CheckItem;
+use database_monitor::check::CheckReport;
+use database_monitor::check::CheckStatus;
+use database_monitor::config::Configuration;
+use database_monitor::worker::WorkerError;
+
+use common::project_path;
+use common::FakeConnector;
+
+fn config(servers: &str) -> Configuration {
+    let json = format!(
+        r#"{{ "address": "localhost", "port": 8080, "interval": 600, "servers": [ {} ] }}"#,
+        servers
+    );
+
+    serde_json::from_str(&json).unwrap()
+}
+
+fn items(report: &CheckReport, status: CheckStatus) -> Vec<(String, String)> {
+    report
+        .items()
+        .iter()
+        .filter(|item| item.status() == status)
+        .map(|item| (item.name().clone(), item.message().clone()))
+        .collect()
+}
+
+#[tokio::test]
+async fn reports_duplicate_servers_and_unknown_primaries() {
+    let config = config(
+        r#"
+        { "host": "alpha", "role": "user", "password": "password" },
+        { "host": "beta", "role": "user", "password": "password", "primary": "gamma" },
+        { "host": "alpha", "port": 5433, "role": "user", "password": "password" }
+        "#,
+    );
+    let report = check_configuration(&config, &FakeConnector::new(), false).await;
+
+    assert!(!report.is_ok());
+    assert_eq!(
+        items(&report, CheckStatus::Error),
+        vec![
+            (
+                "server alpha".to_string(),
+                "defined 2 times (entries 1, 3 of servers)".to_string()
+            ),
+            (
+                "server beta".to_string(),
+                "primary gamma is not defined in servers".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        items(&report, CheckStatus::Skipped),
+        vec![
+            (
+                "metadata query".to_string(),
+                "metadata is not configured".to_string()
+            ),
+            (
+                "connections".to_string(),
+                "use --check-connections to connect to every server".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn reports_every_server_connection() {
+    let connector = FakeConnector::new();
+    let config = config(
+        r#"
+        { "host": "alpha", "role": "user", "password": "password" },
+        { "host": "beta", "role": "user", "password": "password" }
+        "#,
+    );
+
+    connector.script_databases("alpha", &["first", "second"]);
+    connector.script_probe_error(
+        "beta",
+        WorkerError::ConnectError {
+            message: "connection refused".into(),
+        },
+    );
+
+    let report = check_configuration(&config, &connector, true).await;
+
+    assert_eq!(report.errors(), 1);
+    assert_eq!(
+        items(&report, CheckStatus::Ok),
+        vec![
+            ("servers".to_string(), "2 servers configured".to_string()),
+            (
+                "connection alpha:5432".to_string(),
+                "version fake, 2 databases".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        items(&report, CheckStatus::Error),
+        vec![(
+            "connection beta:5432".to_string(),
+            "connection refused".to_string()
+        )]
+    );
+}
+
+#[test]
+fn report_lists_one_item_per_line() {
+    let mut report = CheckReport::new();
+    let mut output = Vec::new();
+
+    report.push(CheckItem::ok("servers", "1 servers configured"));
+    report.push(CheckItem::warning("metadata query", "1 extra columns are ignored"));
+    report.write(&mut output).unwrap();
+
+    assert!(report.is_ok());
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "ok       servers: 1 servers configured\n\
+         warning  metadata query: 1 extra columns are ignored\n"
+    );
+}
+
+#[tokio::test]
+async fn reports_management_without_auth() {
+    let json = r#"{
+        "address": "localhost",
+        "port": 8080,
+        "interval": 600,
+        "servers": [],
+        "management": { "protected": [] }
+    }"#;
+    let config: Configuration = serde_json::from_str(json).unwrap();
+    let report = check_configuration(&config, &FakeConnector::new(), false).await;
+
+    assert_eq!(
+        items(&report, CheckStatus::Error),
+        vec![(
+            "management".to_string(),
+            "management mode requires auth section".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn reports_missing_files_and_directories() {
+    let directory = env::temp_dir();
+    let config_path = directory.join(format!("database-monitor-{}-check.json", process::id()));
+    let config = format!(
+        r#"{{
+            "address": "localhost",
+            "port": 8080,
+            "interval": 600,
+            "servers": [],
+            "tls": {{ "certificate": "{}", "key": "/missing/key.pem" }},
+            "auth": {{ "htpasswd": "/missing/users.htpasswd" }},
+            "management": {{ "protected": [], "audit_log": "/missing/audit.log" }},
+            "state_file": "{}"
+        }}"#,
+        project_path("tests/tls/certificate.pem").display(),
+        directory.join("database-monitor-state.json").display()
+    );
+
+    fs::write(&config_path, config).unwrap();
+
+    let args = vec![
+        "database-monitor".to_string(),
+        "--config".to_string(),
+        config_path.display().to_string(),
+        "--assets-dir".to_string(),
+        "/missing/assets".to_string(),
+    ];
+    let config = Configuration::from_args_and_env(args, Vec::new()).unwrap();
+    let report = check_configuration(&config, &FakeConnector::new(), false).await;
+    let names = |status| {
+        items(&report, status)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+    };
+
+    let _ = fs::remove_file(&config_path);
+
+    assert_eq!(
+        names(CheckStatus::Ok),
+        vec!["management", "tls certificate", "state file"]
+    );
+    assert_eq!(
+        names(CheckStatus::Error),
+        vec!["tls key", "htpasswd", "audit log", "assets directory"]
+    );
+    assert!(items(&report, CheckStatus::Error)
+        .iter()
+        .all(|(_, message)| message.starts_with("/missing")));
+}
+
+#[tokio::test]
+async fn reports_invalid_trusted_proxies() {
+    let check = |fields: &str| {
+        let json = format!(
+            r#"{{ "address": "localhost", "port": 8080, "interval": 600, "servers": []{} }}"#,
+            fields
+        );
+        let config: Configuration = serde_json::from_str(&json).unwrap();
+
+        async move {
+            check_configuration(&config, &FakeConnector::new(), false)
+                .await
+                .items()
+                .iter()
+                .filter(|item| item.name() == "trusted proxies")
+                .map(|item| (item.status(), item.message().clone()))
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert!(check("").await.is_empty());
+    assert_eq!(
+        check(r#", "trusted_proxies": [ "10.0.0.1", "::1" ]"#).await,
+        vec![(CheckStatus::Ok, "2 trusted proxies".to_string())]
+    );
+    assert_eq!(
+        check(r#", "trusted_proxies": [ "proxy.example.com" ]"#).await,
+        vec![(
+            CheckStatus::Error,
+            "Invalid trusted proxy address proxy.example.com: invalid IP address syntax"
+                .to_string()
+        )]
+    );
+    assert_eq!(
+        check(r#", "auth": { "proxy": { "header": "X-Forwarded-User" } }"#).await,
+        vec![(
+            CheckStatus::Warning,
+            "proxy authentication is configured, but trusted_proxies is empty".to_string()
+        )]
+    );
+}
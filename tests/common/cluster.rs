@@ -0,0 +1,191 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use postgres::Client;
+use postgres::NoTls;
+
+use super::free_port;
+
+pub const TEST_URL_VARIABLE: &str = "DATABASE_MONITOR_TEST_URL";
+
+static CLUSTER_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+pub struct TestCluster {
+    host: String,
+    port: u16,
+    user: String,
+    password: String,
+    server: Option<Child>,
+    data_dir: Option<PathBuf>,
+}
+
+fn parse_url(url: &str) -> Option<(String, u16, String, String)> {
+    let rest = url.split_once("://")?.1;
+    let (credentials, address) = match rest.rfind('@') {
+        Some(index) => (&rest[..index], &rest[index + 1..]),
+        None => ("postgres", rest),
+    };
+    let address = address.split('/').next()?;
+    let mut credentials = credentials.splitn(2, ':');
+    let user = credentials.next()?.to_string();
+    let password = credentials.next().unwrap_or("").to_string();
+    let mut address = address.splitn(2, ':');
+    let host = address.next()?.to_string();
+    let port = match address.next() {
+        Some(port) => port.parse().ok()?,
+        None => 5432,
+    };
+
+    Some((host, port, user, password))
+}
+
+impl TestCluster {
+    pub fn start() -> TestCluster {
+        match env::var(TEST_URL_VARIABLE) {
+            Ok(url) => {
+                let (host, port, user, password) = parse_url(&url)
+                    .unwrap_or_else(|| panic!("Invalid {}: {}", TEST_URL_VARIABLE, url));
+
+                TestCluster {
+                    host,
+                    port,
+                    user,
+                    password,
+                    server: None,
+                    data_dir: None,
+                }
+            }
+            Err(_) => TestCluster::initdb(),
+        }
+    }
+
+    fn initdb() -> TestCluster {
+        let data_dir = env::temp_dir().join(format!(
+            "database-monitor-cluster-{}-{}",
+            process::id(),
+            CLUSTER_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let initialized = Command::new("initdb")
+            .arg("-D")
+            .arg(&data_dir)
+            .arg("-U")
+            .arg("postgres")
+            .arg("-A")
+            .arg("trust")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+
+        if !initialized {
+            let _ = fs::remove_dir_all(&data_dir);
+
+            panic!(
+                "initdb failed, put PostgreSQL binaries to PATH or set {} to use existing server",
+                TEST_URL_VARIABLE
+            );
+        }
+
+        let port = free_port();
+        let server = Command::new("postgres")
+            .arg("-D")
+            .arg(&data_dir)
+            .arg("-p")
+            .arg(port.to_string())
+            .arg("-k")
+            .arg(&data_dir)
+            .arg("-c")
+            .arg("listen_addresses=127.0.0.1")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to start postgres");
+        let cluster = TestCluster {
+            host: "127.0.0.1".into(),
+            port,
+            user: "postgres".into(),
+            password: "postgres".into(),
+            server: Some(server),
+            data_dir: Some(data_dir),
+        };
+
+        cluster.wait_ready(Duration::from_secs(30));
+
+        cluster
+    }
+
+    fn wait_ready(&self, timeout: Duration) {
+        let start = Instant::now();
+
+        loop {
+            match Client::connect(&self.url("postgres"), NoTls) {
+                Ok(_) => return,
+                Err(err) => {
+                    if start.elapsed() > timeout {
+                        panic!("PostgreSQL did not start: {}", err);
+                    }
+                }
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    pub fn host(&self) -> &String {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn user(&self) -> &String {
+        &self.user
+    }
+
+    pub fn password(&self) -> &String {
+        &self.password
+    }
+
+    pub fn url(&self, database: &str) -> String {
+        format!(
+            "postgresql://{}:{}@{}:{}/{}",
+            self.user, self.password, self.host, self.port, database
+        )
+    }
+
+    pub fn connect(&self, database: &str) -> Client {
+        Client::connect(&self.url(database), NoTls).expect("Failed to connect")
+    }
+
+    pub fn server_json(&self) -> String {
+        format!(
+            r#"{{ "host": "{}", "port": {}, "role": "{}", "password": "{}" }}"#,
+            self.host, self.port, self.user, self.password
+        )
+    }
+}
+
+impl Drop for TestCluster {
+    fn drop(&mut self) {
+        if let Some(ref mut server) = self.server {
+            let _ = server.kill();
+            let _ = server.wait();
+        }
+
+        if let Some(ref data_dir) = self.data_dir {
+            let _ = fs::remove_dir_all(data_dir);
+        }
+    }
+}
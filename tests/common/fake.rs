@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use database_monitor::config::MetadataConnInfo;
+use database_monitor::config::ServerConnInfo;
+use database_monitor::state::DatabaseInfo;
+use database_monitor::state::ServerDetails;
+use database_monitor::worker::Connector;
+use database_monitor::worker::DatabaseMetadata;
+use database_monitor::worker::ServerDatabase;
+use database_monitor::worker::WorkerError;
+use database_monitor::worker::WorkerResult;
+
+#[derive(Default)]
+pub struct FakeConnector {
+    servers: Mutex<HashMap<String, VecDeque<WorkerResult<Vec<String>>>>>,
+    delays: Mutex<HashMap<String, Duration>>,
+    metadata: Mutex<VecDeque<WorkerResult<Vec<DatabaseMetadata>>>>,
+    metadata_delay: Mutex<Option<Duration>>,
+    probes: Mutex<Vec<String>>,
+    metadata_requests: Mutex<Vec<Vec<ServerDatabase>>>,
+}
+
+fn unscripted(what: &str) -> WorkerError {
+    WorkerError::ConnectError {
+        message: format!("No scripted result for {}", what),
+    }
+}
+
+impl FakeConnector {
+    pub fn new() -> FakeConnector {
+        FakeConnector::default()
+    }
+
+    pub fn script_databases(&self, server_name: &str, databases: &[&str]) {
+        let databases = databases.iter().map(|name| name.to_string()).collect();
+
+        self.script_probe(server_name, Ok(databases));
+    }
+
+    pub fn script_probe_error(&self, server_name: &str, error: WorkerError) {
+        self.script_probe(server_name, Err(error));
+    }
+
+    pub fn script_probe_delay(&self, server_name: &str, delay: Duration) {
+        self.delays.lock().unwrap().insert(server_name.into(), delay);
+    }
+
+    fn script_probe(&self, server_name: &str, result: WorkerResult<Vec<String>>) {
+        self.servers
+            .lock()
+            .unwrap()
+            .entry(server_name.into())
+            .or_default()
+            .push_back(result);
+    }
+
+    pub fn script_metadata(&self, metadata: Vec<DatabaseMetadata>) {
+        self.metadata.lock().unwrap().push_back(Ok(metadata));
+    }
+
+    pub fn script_metadata_error(&self, error: WorkerError) {
+        self.metadata.lock().unwrap().push_back(Err(error));
+    }
+
+    pub fn script_metadata_delay(&self, delay: Duration) {
+        *self.metadata_delay.lock().unwrap() = Some(delay);
+    }
+
+    pub fn probes(&self) -> Vec<String> {
+        self.probes.lock().unwrap().clone()
+    }
+
+    pub fn metadata_requests(&self) -> Vec<Vec<ServerDatabase>> {
+        self.metadata_requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Connector for FakeConnector {
+    async fn probe_server(
+        &self,
+        connection_info: &ServerConnInfo,
+        _timeout: Duration,
+    ) -> WorkerResult<(ServerDetails, Vec<DatabaseInfo>)> {
+        let server_name = connection_info.host();
+        let result = self
+            .servers
+            .lock()
+            .unwrap()
+            .get_mut(server_name)
+            .and_then(|results| results.pop_front())
+            .unwrap_or_else(|| Err(unscripted(server_name)));
+
+        self.probes.lock().unwrap().push(server_name.clone());
+
+        let delay = self.delays.lock().unwrap().get(server_name).cloned();
+
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        let names = result?;
+        let details = ServerDetails::new("fake", 0, false, None, 100, Vec::new());
+        let databases = names
+            .iter()
+            .map(|name| DatabaseInfo::new(server_name, name, "C", "owner", 0))
+            .collect();
+
+        Ok((details, databases))
+    }
+
+    async fn query_metadata(
+        &self,
+        _: &MetadataConnInfo,
+        databases: &[ServerDatabase],
+    ) -> WorkerResult<Vec<DatabaseMetadata>> {
+        let result = self
+            .metadata
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Err(unscripted("metadata")));
+
+        self.metadata_requests
+            .lock()
+            .unwrap()
+            .push(databases.to_vec());
+
+        let delay = *self.metadata_delay.lock().unwrap();
+
+        if let Some(delay) = delay {
+            tokio::time::sleep(delay).await;
+        }
+
+        result
+    }
+}
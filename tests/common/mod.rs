@@ -0,0 +1,170 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod cluster;
+mod fake;
+
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+pub use self::cluster::TestCluster;
+pub use self::fake::FakeConnector;
+
+pub struct Monitor {
+    child: Child,
+    config_path: PathBuf,
+}
+
+pub fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|address| address.port())
+        .expect("Failed to allocate port")
+}
+
+pub fn project_path(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path)
+}
+
+pub fn wait_until<F>(timeout: Duration, mut condition: F) -> bool
+where
+    F: FnMut() -> bool,
+{
+    let start = Instant::now();
+
+    while start.elapsed() < timeout {
+        if condition() {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    false
+}
+
+pub async fn wait_until_async<F>(timeout: Duration, mut condition: F) -> bool
+where
+    F: FnMut() -> bool,
+{
+    let start = Instant::now();
+
+    while start.elapsed() < timeout {
+        if condition() {
+            return true;
+        }
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    false
+}
+
+pub fn wait_for_port(port: u16, timeout: Duration) -> bool {
+    let start = Instant::now();
+
+    while start.elapsed() < timeout {
+        if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+            return true;
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    false
+}
+
+pub fn http_get(port: u16, path: &str) -> (u16, String) {
+    http_get_with_headers(port, path, &[])
+}
+
+pub fn http_get_with_headers(port: u16, path: &str, headers: &[(&str, &str)]) -> (u16, String) {
+    http_request(port, "GET", path, headers)
+}
+
+pub fn http_request(
+    port: u16,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("Failed to connect");
+    let headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    let request = format!(
+        "{} {} HTTP/1.1\r\nHost: 127.0.0.1\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+        method, path, headers
+    );
+    let mut response = String::new();
+
+    stream
+        .write_all(request.as_bytes())
+        .and_then(|_| stream.read_to_string(&mut response))
+        .expect("Failed to read response");
+
+    let status = response
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .expect("Invalid status line");
+    let body = match response.find("\r\n\r\n") {
+        Some(index) => response[index + 4..].to_string(),
+        None => String::new(),
+    };
+
+    (status, body)
+}
+
+impl Monitor {
+    pub fn start(name: &str, config: &str) -> Monitor {
+        let config_path = env::temp_dir().join(format!(
+            "database-monitor-{}-{}.json",
+            name,
+            std::process::id()
+        ));
+
+        File::create(&config_path)
+            .and_then(|mut file| file.write_all(config.as_bytes()))
+            .expect("Failed to write configuration");
+
+        let child = Command::new(env!("CARGO_BIN_EXE_database-monitor"))
+            .arg("--config")
+            .arg(&config_path)
+            .current_dir(env::temp_dir())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to start database monitor");
+
+        Monitor { child, config_path }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    pub fn child_mut(&mut self) -> &mut Child {
+        &mut self.child
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_file(&self.config_path);
+    }
+}
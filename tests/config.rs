@@ -0,0 +1,199 @@
+use std::env;
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::process;
+
+use database_monitor::config::ConfigFormat;
+use database_monitor::config::Configuration;
+
+const JSON: &str = r#"{
+    "address": "127.0.0.1",
+    "port": 8000,
+    "interval": 300,
+    "metadata": {
+        "host": "metadata",
+        "database": "metadata",
+        "role": "metadata",
+        "password": "metadata",
+        "query": "SELECT 1"
+    },
+    "servers": [ { "host": "alpha", "role": "user", "password": "password" } ]
+}"#;
+
+const TOML: &str = r#"
+# Comments are allowed
+address = "127.0.0.1"
+port = 8000
+interval = 300
+
+[metadata]
+host = "metadata"
+database = "metadata"
+role = "metadata"
+password = "metadata"
+query = "SELECT 1"
+
+[[servers]]
+host = "alpha"
+role = "user"
+password = "password"
+quiet_hours = { from = "01:00", to = "05:00" }
+"#;
+
+const YAML: &str = r#"
+# Comments are allowed
+address: 127.0.0.1
+port: 8000
+interval: 300
+metadata:
+  host: metadata
+  database: metadata
+  role: metadata
+  password: metadata
+  query: SELECT 1
+servers:
+  - host: alpha
+    role: user
+    password: password
+"#;
+
+struct ConfigFile {
+    path: PathBuf,
+}
+
+impl ConfigFile {
+    fn new(name: &str, content: &str) -> ConfigFile {
+        let path = env::temp_dir().join(format!("database-monitor-{}-{}", process::id(), name));
+
+        fs::write(&path, content).unwrap();
+
+        ConfigFile { path }
+    }
+
+    fn load(&self, flags: &[&str], environment: &[(&str, &str)]) -> Configuration {
+        let mut args = vec![
+            "database-monitor".to_string(),
+            "--config".to_string(),
+            self.path.display().to_string(),
+        ];
+        args.extend(flags.iter().map(|flag| flag.to_string()));
+
+        let environment = environment
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        Configuration::from_args_and_env(args, environment).unwrap()
+    }
+}
+
+impl Drop for ConfigFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[test]
+fn format_is_detected_by_extension() {
+    assert_eq!(ConfigFormat::from_path("config.json"), ConfigFormat::Json);
+    assert_eq!(ConfigFormat::from_path("config.toml"), ConfigFormat::Toml);
+    assert_eq!(ConfigFormat::from_path("config.yaml"), ConfigFormat::Yaml);
+    assert_eq!(ConfigFormat::from_path("config.yml"), ConfigFormat::Yaml);
+    assert_eq!(ConfigFormat::from_path("config"), ConfigFormat::Json);
+}
+
+#[test]
+fn all_formats_are_equivalent() {
+    for (name, content) in &[("a.json", JSON), ("a.toml", TOML), ("a.yml", YAML)] {
+        let config = ConfigFile::new(name, content).load(&[], &[]);
+        let metadata = config.metadata().as_ref().unwrap();
+
+        assert_eq!(config.address(), "127.0.0.1", "{}", name);
+        assert_eq!(config.port(), 8000, "{}", name);
+        assert_eq!(config.interval(), 300, "{}", name);
+        assert_eq!(metadata.query(), "SELECT 1", "{}", name);
+        assert_eq!(config.servers().len(), 1, "{}", name);
+        assert_eq!(config.servers()[0].host(), "alpha", "{}", name);
+    }
+}
+
+#[test]
+fn environment_overrides_file() {
+    let file = ConfigFile::new("env.toml", TOML);
+    let config = file.load(
+        &[],
+        &[
+            ("DBMON_ADDRESS", "0.0.0.0"),
+            ("DBMON_PORT", "9000"),
+            ("DBMON_BASE_PATH", "/dbmon"),
+            ("DBMON_METADATA_PORT", "5433"),
+            ("DBMON_METADATA_PASSWORD", "secret"),
+            ("DBMON_TRUSTED_PROXIES", "127.0.0.1, ::1,"),
+            ("HOME", "/root"),
+        ],
+    );
+    let metadata = config.metadata().as_ref().unwrap();
+
+    assert_eq!(config.address(), "0.0.0.0");
+    assert_eq!(config.port(), 9000);
+    assert_eq!(config.interval(), 300);
+    assert_eq!(config.base_path().as_str(), "/dbmon");
+    assert_eq!(
+        config.trusted_proxies().unwrap(),
+        vec![IpAddr::from([127, 0, 0, 1]), IpAddr::from([0, 0, 0, 0, 0, 0, 0, 1])]
+    );
+    assert_eq!(metadata.port(), 5433);
+    assert_eq!(metadata.password(), "secret");
+    assert_eq!(metadata.role(), "metadata");
+}
+
+#[test]
+fn environment_can_define_metadata() {
+    let file = ConfigFile::new(
+        "metadata.yaml",
+        "address: localhost\nport: 8000\ninterval: 60\nservers: []\n",
+    );
+    let config = file.load(
+        &[],
+        &[
+            ("DBMON_METADATA_HOST", "metadata"),
+            ("DBMON_METADATA_DATABASE", "metadata"),
+            ("DBMON_METADATA_ROLE", "metadata"),
+            ("DBMON_METADATA_PASSWORD", "metadata"),
+            ("DBMON_METADATA_QUERY", "SELECT 2"),
+        ],
+    );
+
+    assert_eq!(config.metadata().as_ref().unwrap().query(), "SELECT 2");
+}
+
+#[test]
+fn command_line_overrides_environment() {
+    let file = ConfigFile::new("cli.json", JSON);
+    let config = file.load(
+        &["--port", "7000", "--interval", "10"],
+        &[
+            ("DBMON_PORT", "9000"),
+            ("DBMON_INTERVAL", "20"),
+            ("DBMON_ADDRESS", "0.0.0.0"),
+        ],
+    );
+
+    assert_eq!(config.port(), 7000);
+    assert_eq!(config.interval(), 10);
+    assert_eq!(config.address(), "0.0.0.0");
+}
+
+#[test]
+fn invalid_environment_value_is_rejected() {
+    let file = ConfigFile::new("invalid.json", JSON);
+    let args = vec![
+        "database-monitor".to_string(),
+        "--config".to_string(),
+        file.path.display().to_string(),
+    ];
+    let environment = vec![("DBMON_PORT".to_string(), "http".to_string())];
+    let err = Configuration::from_args_and_env(args, environment).unwrap_err();
+
+    assert!(err.to_string().contains("DBMON_PORT"), "{}", err);
+}
@@ -0,0 +1,85 @@
+use database_monitor::export::write_rows;
+use database_monitor::export::ExportChunks;
+use database_monitor::export::ExportFormat;
+use database_monitor::state::DatabaseRow;
+
+fn row() -> DatabaseRow {
+    let mut row = DatabaseRow::new("primary", &None, "app_db", "C", "owner")
+        .with_metadata(&Some(42), &Some("main".into()), &None)
+        .with_times(100, 50, None)
+        .with_flags(&["stale".into(), "orphaned".into()]);
+    row.set_replicas(vec!["replica-1".into(), "replica-2".into()]);
+    row
+}
+
+fn export(format: ExportFormat, rows: &[DatabaseRow]) -> String {
+    let mut output = Vec::new();
+    write_rows(format, rows, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn empty_csv_export_has_header() {
+    let output = export(ExportFormat::Csv, &[]);
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("server_name,server_description,database_name,"));
+    assert!(lines[0].contains(",flags,replicas,"));
+}
+
+#[test]
+fn csv_export_joins_flags_and_replicas() {
+    let output = export(ExportFormat::Csv, &[row()]);
+    let mut reader = csv::Reader::from_reader(output.as_bytes());
+    let headers = reader.headers().unwrap().clone();
+    let record = reader.records().next().unwrap().unwrap();
+
+    let field = |name: &str| {
+        let index = headers.iter().position(|header| header == name).unwrap();
+        record.get(index).unwrap().to_string()
+    };
+
+    assert_eq!(field("database_name"), "app_db");
+    assert_eq!(field("flags"), "stale;orphaned");
+    assert_eq!(field("replicas"), "replica-1;replica-2");
+}
+
+#[test]
+fn csv_header_matches_json_keys() {
+    let csv = export(ExportFormat::Csv, &[]);
+    let header: Vec<&str> = csv.lines().next().unwrap().split(',').collect();
+
+    let json = export(ExportFormat::JsonLines, &[row()]);
+    let value: serde_json::Value = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+    let keys: Vec<&str> = value.as_object().unwrap().keys().map(|key| key.as_str()).collect();
+
+    let mut sorted_header = header.clone();
+    sorted_header.sort();
+    let mut sorted_keys = keys.clone();
+    sorted_keys.sort();
+
+    assert_eq!(sorted_header, sorted_keys);
+    assert_eq!(value["flags"], serde_json::json!(["stale", "orphaned"]));
+    assert_eq!(value["replicas"], serde_json::json!(["replica-1", "replica-2"]));
+}
+
+#[test]
+fn chunked_export_matches_buffered_export() {
+    let rows = || (0..600).map(|_| row()).collect::<Vec<DatabaseRow>>();
+
+    for format in [ExportFormat::Csv, ExportFormat::JsonLines, ExportFormat::Json] {
+        let chunks: Vec<Vec<u8>> = ExportChunks::new(format, rows())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert!(chunks.len() > 3);
+        assert_eq!(String::from_utf8(chunks.concat()).unwrap(), export(format, &rows()));
+    }
+
+    let empty: Vec<Vec<u8>> = ExportChunks::new(ExportFormat::Json, Vec::new())
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(empty.concat(), b"[]");
+}
@@ -0,0 +1,139 @@
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+
+use serde_json::Value;
+
+use database_monitor::config::Configuration;
+use database_monitor::management::ManagementError;
+use database_monitor::management::Manager;
+
+const SERVERS: &str = r#"[
+    {
+        "host": "127.0.0.2",
+        "port": 1,
+        "role": "user",
+        "password": "password",
+        "management": { "role": "admin", "password": "password" }
+    },
+    { "host": "127.0.0.3", "port": 1, "role": "user", "password": "password" }
+]"#;
+
+const AUTH: &str = r#"{ "tokens": [ { "user": "dba", "token": "secret" } ] }"#;
+
+fn config(auth: Option<&str>, audit_log: &Path) -> Configuration {
+    let mut json = format!(
+        r#"{{
+            "address": "127.0.0.1",
+            "port": 8080,
+            "interval": 600,
+            "servers": {},
+            "management": {{ "protected": [ "release_*" ], "audit_log": "{}" }}"#,
+        SERVERS,
+        audit_log.display()
+    );
+
+    if let Some(auth) = auth {
+        json.push_str(&format!(r#", "auth": {}"#, auth));
+    }
+
+    json.push('}');
+
+    serde_json::from_str(&json).unwrap()
+}
+
+fn audit_log(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!(
+        "database-monitor-{}-{}.audit.log",
+        name,
+        process::id()
+    ));
+    let _ = fs::remove_file(&path);
+
+    path
+}
+
+fn audit_entries(path: &Path) -> Vec<Value> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn manager(name: &str) -> (Manager, PathBuf) {
+    let path = audit_log(name);
+    let manager = Manager::new(&config(Some(AUTH), &path)).unwrap().unwrap();
+
+    (manager, path)
+}
+
+#[test]
+fn management_requires_auth() {
+    let path = audit_log("management-auth");
+    let err = Manager::new(&config(None, &path)).unwrap_err();
+
+    assert!(format!("{}", err).contains("auth"));
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn protected_databases_and_unmanaged_servers_are_rejected() {
+    let (manager, path) = manager("management-protected");
+
+    for database in &["postgres", "template0", "template1", "release_1"] {
+        match manager.plan("127.0.0.2", database, Some("dba"), "127.0.0.1").await {
+            Err(ManagementError::ProtectedDatabase { .. }) => {}
+            other => panic!("Unexpected plan result for {}: {:?}", database, other),
+        }
+    }
+
+    match manager.plan("127.0.0.3", "ci", Some("dba"), "127.0.0.1").await {
+        Err(ManagementError::ServerNotManaged { .. }) => {}
+        other => panic!("Unexpected plan result: {:?}", other),
+    }
+
+    match manager
+        .drop_database("127.0.0.2", "release_1", "token", Some("dba"), "127.0.0.1")
+        .await
+    {
+        Err(ManagementError::ProtectedDatabase { .. }) => {}
+        other => panic!("Unexpected drop result: {:?}", other),
+    }
+
+    let entries = audit_entries(&path);
+
+    assert_eq!(entries.len(), 6);
+    assert_eq!(entries[5]["action"], "drop");
+    assert_eq!(entries[5]["user"], "dba");
+    assert_eq!(entries[5]["database"], "release_1");
+
+    let _ = fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn drop_requires_confirmation_token() {
+    let (manager, path) = manager("management-confirm");
+
+    for token in &["", "token", "00000000000000000000000000000000"] {
+        match manager
+            .drop_database("127.0.0.2", "ci", token, Some("dba"), "127.0.0.1")
+            .await
+        {
+            Err(ManagementError::ConfirmationRequired) => {}
+            other => panic!("Unexpected drop result for {:?}: {:?}", token, other),
+        }
+    }
+
+    let entries = audit_entries(&path);
+
+    assert_eq!(entries.len(), 3);
+    assert!(entries
+        .iter()
+        .all(|entry| entry["result"] != "ok" && entry["backends"].is_null()));
+
+    let _ = fs::remove_file(&path);
+}
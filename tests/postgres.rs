@@ -0,0 +1,576 @@
+mod common;
+
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::runtime::Runtime;
+
+use database_monitor::check::check_configuration;
+use database_monitor::check::CheckStatus;
+use database_monitor::config::Configuration;
+use database_monitor::config::MetadataConnInfo;
+use database_monitor::config::ServerConnInfo;
+use database_monitor::management::ManagementError;
+use database_monitor::management::Manager;
+use database_monitor::search::Query;
+use database_monitor::state::DatabaseRow;
+use database_monitor::state::State;
+use database_monitor::state::Visibility;
+use database_monitor::worker::Connector;
+use database_monitor::worker::DatabaseWorker;
+use database_monitor::worker::DefaultConnector;
+use database_monitor::worker::probe_server;
+use database_monitor::worker::MetadataWorker;
+use database_monitor::worker::ServerDatabase;
+
+use common::free_port;
+use common::http_get;
+use common::wait_for_port;
+use common::wait_until;
+use common::wait_until_async;
+use common::Monitor;
+use common::TestCluster;
+
+static FIXTURE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+const TIMEOUT: u64 = 20;
+
+struct Fixture {
+    cluster: TestCluster,
+    tag: String,
+}
+
+impl Fixture {
+    fn setup() -> Fixture {
+        let cluster = TestCluster::start();
+        let tag = format!(
+            "dbmon{}t{}",
+            process::id(),
+            FIXTURE_COUNTER.fetch_add(1, Ordering::SeqCst)
+        );
+        let fixture = Fixture { cluster, tag };
+        let mut conn = fixture.cluster.connect("postgres");
+
+        for statement in &[
+            format!("CREATE ROLE {}_owner LOGIN", fixture.tag),
+            format!("CREATE ROLE {}_creator LOGIN CREATEROLE", fixture.tag),
+            format!("CREATE ROLE {}_nologin NOLOGIN", fixture.tag),
+            format!("CREATE DATABASE {0}_owned OWNER {0}_owner", fixture.tag),
+            format!("CREATE DATABASE {0}_creator OWNER {0}_creator", fixture.tag),
+            format!("CREATE DATABASE {0}_nologin OWNER {0}_nologin", fixture.tag),
+            format!("CREATE DATABASE {}_metadata", fixture.tag),
+        ] {
+            conn.execute(statement, &[]).expect(statement);
+        }
+
+        let mut metadata = fixture.cluster.connect(&fixture.metadata_database());
+
+        metadata
+            .batch_execute(
+                "CREATE TABLE monitor (
+                    server_name TEXT NOT NULL,
+                    database_name TEXT NOT NULL,
+                    commit BIGINT NOT NULL,
+                    branch TEXT NOT NULL,
+                    project TEXT NOT NULL
+                )",
+            )
+            .expect("Failed to create metadata table");
+        metadata
+            .execute(
+                "INSERT INTO monitor VALUES ($1, $2, 1024, 'master', 'project')",
+                &[fixture.cluster.host(), &fixture.database("owned")],
+            )
+            .expect("Failed to insert metadata");
+
+        fixture
+    }
+
+    fn database(&self, suffix: &str) -> String {
+        format!("{}_{}", self.tag, suffix)
+    }
+
+    fn metadata_database(&self) -> String {
+        self.database("metadata")
+    }
+
+    fn server_info(&self) -> ServerConnInfo {
+        serde_json::from_str(&self.cluster.server_json()).unwrap()
+    }
+
+    fn metadata_info(&self) -> MetadataConnInfo {
+        let json = self.metadata_json(
+            "SELECT commit, branch, project FROM monitor \
+             WHERE server_name = $1 AND database_name = $2",
+        );
+
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn metadata_json(&self, query: &str) -> Value {
+        serde_json::json!({
+            "host": self.cluster.host(),
+            "port": self.cluster.port(),
+            "database": self.metadata_database(),
+            "role": self.cluster.user(),
+            "password": self.cluster.password(),
+            "query": query,
+        })
+    }
+
+    fn config_with_query(&self, query: &str) -> Configuration {
+        let json = serde_json::json!({
+            "address": "127.0.0.1",
+            "port": 8080,
+            "interval": 600,
+            "metadata": self.metadata_json(query),
+            "servers": [],
+        });
+
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn manager(&self) -> Manager {
+        let json = serde_json::json!({
+            "address": "127.0.0.1",
+            "port": 8080,
+            "interval": 600,
+            "servers": [{
+                "host": self.cluster.host(),
+                "port": self.cluster.port(),
+                "role": self.cluster.user(),
+                "password": self.cluster.password(),
+                "management": {
+                    "role": self.cluster.user(),
+                    "password": self.cluster.password(),
+                },
+            }],
+            "auth": { "tokens": [ { "user": "dba", "token": "secret" } ] },
+            "management": { "protected": [ self.database("metadata") ] },
+        });
+        let config: Configuration = serde_json::from_value(json).unwrap();
+
+        Manager::new(&config).unwrap().unwrap()
+    }
+
+    fn database_exists(&self, suffix: &str) -> bool {
+        let mut conn = self.cluster.connect("postgres");
+        let rows = conn
+            .query(
+                "SELECT 1 FROM pg_database WHERE datname = $1",
+                &[&self.database(suffix)],
+            )
+            .unwrap();
+
+        !rows.is_empty()
+    }
+
+    fn query(&self, state: &State) -> Vec<DatabaseRow> {
+        let mut rows = state.query(&Query::new(&[&self.tag]), &Visibility::All, false);
+        rows.sort_by(|a, b| a.database_name().cmp(b.database_name()));
+
+        rows
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let mut conn = self.cluster.connect("postgres");
+        let _ = conn.execute(
+            &format!("DROP SCHEMA IF EXISTS {} CASCADE", self.database("replicas")),
+            &[],
+        );
+
+        for suffix in &["owned", "creator", "nologin", "metadata", "dropped"] {
+            let _ = conn.execute(
+                &format!("DROP DATABASE IF EXISTS {}", self.database(suffix)),
+                &[],
+            );
+        }
+
+        for suffix in &["owner", "creator", "nologin", "monitor"] {
+            let _ = conn.execute(
+                &format!("DROP ROLE IF EXISTS {}", self.database(suffix)),
+                &[],
+            );
+        }
+    }
+}
+
+fn connector() -> Arc<dyn Connector> {
+    Arc::new(DefaultConnector)
+}
+
+fn runtime() -> Runtime {
+    Runtime::new().expect("Failed to start runtime")
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn database_worker_lists_databases_of_login_roles() {
+    let fixture = Fixture::setup();
+
+    runtime().block_on(async {
+        let state = State::default();
+        let worker = DatabaseWorker::spawn(
+            vec![fixture.server_info()],
+            600,
+            30,
+            state.clone(),
+            connector(),
+            None,
+        );
+
+        assert!(
+            wait_until_async(Duration::from_secs(TIMEOUT), || !fixture
+                .query(&state)
+                .is_empty())
+            .await
+        );
+
+        let rows = fixture.query(&state);
+        let names: Vec<&String> = rows.iter().map(|row| row.database_name()).collect();
+
+        assert_eq!(names, vec![&fixture.database("owned")]);
+        assert_eq!(rows[0].server_name(), fixture.cluster.host());
+        assert_eq!(rows[0].database_owner(), &fixture.database("owner"));
+
+        worker.shutdown();
+        assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+    });
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn metadata_worker_sets_database_metadata() {
+    let fixture = Fixture::setup();
+
+    runtime().block_on(async {
+        let state = State::default();
+        let database_worker = DatabaseWorker::spawn(
+            vec![fixture.server_info()],
+            600,
+            30,
+            state.clone(),
+            connector(),
+            None,
+        );
+
+        assert!(
+            wait_until_async(Duration::from_secs(TIMEOUT), || !fixture
+                .query(&state)
+                .is_empty())
+            .await
+        );
+
+        let metadata_worker =
+            MetadataWorker::spawn(fixture.metadata_info(), 600, state.clone(), connector());
+
+        assert!(
+            wait_until_async(Duration::from_secs(TIMEOUT), || {
+                fixture
+                    .query(&state)
+                    .iter()
+                    .any(|row| row.commit().is_some())
+            })
+            .await
+        );
+
+        let rows = fixture.query(&state);
+
+        assert_eq!(rows[0].commit(), &Some(1024));
+        assert_eq!(rows[0].branch_name(), &Some("master".to_string()));
+        assert_eq!(rows[0].project_name(), &Some("project".to_string()));
+
+        database_worker.shutdown();
+        metadata_worker.shutdown();
+        assert!(
+            database_worker
+                .join_timeout(Duration::from_secs(TIMEOUT))
+                .await
+        );
+        assert!(
+            metadata_worker
+                .join_timeout(Duration::from_secs(TIMEOUT))
+                .await
+        );
+    });
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn http_handlers_return_probed_databases() {
+    let fixture = Fixture::setup();
+    let port = free_port();
+    let config = format!(
+        r#"{{
+            "address": "127.0.0.1",
+            "port": {},
+            "interval": 600,
+            "servers": [ {} ]
+        }}"#,
+        port,
+        fixture.cluster.server_json()
+    );
+    let _monitor = Monitor::start(&fixture.tag, &config);
+    let path = format!("/api/v1/databases?q={}", fixture.tag);
+    let mut response = Value::Null;
+
+    assert!(wait_for_port(port, Duration::from_secs(TIMEOUT)));
+    assert!(wait_until(Duration::from_secs(TIMEOUT), || {
+        let (status, body) = http_get(port, &path);
+
+        assert_eq!(status, 200);
+        response = serde_json::from_str(&body).unwrap();
+        response["databases"]
+            .as_array()
+            .is_some_and(|databases| !databases.is_empty())
+    }));
+
+    let databases = response["databases"].as_array().unwrap();
+
+    assert_eq!(response["ok"], Value::Bool(true));
+    assert_eq!(databases.len(), 1);
+    assert_eq!(
+        databases[0]["database_name"],
+        fixture.database("owned").as_str()
+    );
+    assert_eq!(
+        databases[0]["role_name"],
+        fixture.database("owner").as_str()
+    );
+    assert_eq!(databases[0]["server_name"], fixture.cluster.host().as_str());
+
+    let (status, body) = http_get(port, &format!("/api/v1/servers/{}", fixture.cluster.host()));
+    let server: Value = serde_json::from_str(&body).unwrap();
+    let roles = server["server"]["roles"].as_array().unwrap();
+
+    assert_eq!(status, 200);
+    assert!(server["server"]["version"].is_string());
+    assert_eq!(server["server"]["in_recovery"], Value::Bool(false));
+    assert!(roles
+        .iter()
+        .any(|role| role["role_name"] == fixture.database("owner").as_str()));
+
+    let (status, _) = http_get(port, "/api/v1/servers/unknown/databases");
+
+    assert_eq!(status, 404);
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn check_config_validates_metadata_query() {
+    let fixture = Fixture::setup();
+
+    runtime().block_on(async {
+        let check = |query: &str| {
+            let config = fixture.config_with_query(query);
+
+            async move {
+                check_configuration(&config, &DefaultConnector, false)
+                    .await
+                    .items()
+                    .iter()
+                    .filter(|item| item.name() == "metadata query")
+                    .map(|item| (item.status(), item.message().clone()))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let valid = check(
+            "SELECT commit, branch, project, NULL::bigint AS created FROM monitor \
+             WHERE server_name = $1 AND database_name = $2",
+        )
+        .await;
+
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].0, CheckStatus::Ok);
+        assert!(valid[0].1.contains("commit int8"), "{}", valid[0].1);
+
+        let wrong_types = check(
+            "SELECT commit::int4, branch, 1 AS project FROM monitor \
+             WHERE server_name = $1 AND length(database_name) = $2",
+        )
+        .await;
+
+        assert_eq!(
+            wrong_types,
+            vec![
+                (
+                    CheckStatus::Error,
+                    "parameter $2 has type int4, expected text".to_string()
+                ),
+                (
+                    CheckStatus::Error,
+                    "column 1 (commit) has type int4, expected bigint".to_string()
+                ),
+                (
+                    CheckStatus::Error,
+                    "column 3 (project) has type int4, expected text".to_string()
+                ),
+            ]
+        );
+
+        let missing_parameter = check("SELECT commit, branch, project FROM monitor").await;
+
+        assert_eq!(
+            missing_parameter,
+            vec![(
+                CheckStatus::Error,
+                "expected 2 parameters ($1 server name, $2 database name), found 0".to_string()
+            )]
+        );
+
+        let invalid = check("SELECT commit FROM missing").await;
+
+        assert_eq!(invalid[0].0, CheckStatus::Error);
+        assert!(invalid[0].1.contains("42P01"), "{}", invalid[0].1);
+    });
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn manager_plans_and_drops_databases() {
+    let fixture = Fixture::setup();
+    let mut conn = fixture.cluster.connect("postgres");
+
+    conn.execute(&format!("CREATE DATABASE {}", fixture.database("dropped")), &[])
+        .unwrap();
+
+    let _backend = fixture.cluster.connect(&fixture.database("dropped"));
+    let manager = fixture.manager();
+    let server = fixture.cluster.host();
+    let database = fixture.database("dropped");
+    let runtime = runtime();
+    let plan = |manager: &Manager| {
+        runtime
+            .block_on(manager.plan(server, &database, Some("dba"), "127.0.0.1"))
+            .unwrap()
+    };
+    let drop_database = |manager: &Manager, confirm: &str| {
+        runtime.block_on(manager.drop_database(
+            server,
+            &database,
+            confirm,
+            Some("dba"),
+            "127.0.0.1",
+        ))
+    };
+
+    let first = plan(&manager);
+
+    assert_eq!(first.backends(), 1);
+    assert_eq!(first.confirm().len(), 32);
+    assert!(first.confirm().chars().all(|c| c.is_ascii_hexdigit()));
+
+    let second = plan(&manager);
+
+    assert_ne!(first.confirm(), second.confirm());
+
+    match drop_database(&manager, first.confirm()) {
+        Err(ManagementError::ConfirmationRequired) => {}
+        result => panic!("Replaced token was accepted: {:?}", result),
+    }
+
+    assert!(fixture.database_exists("dropped"));
+    assert_eq!(drop_database(&manager, second.confirm()).unwrap(), 1);
+    assert!(!fixture.database_exists("dropped"));
+
+    match drop_database(&manager, second.confirm()) {
+        Err(ManagementError::ConfirmationRequired) => {}
+        result => panic!("Token was used twice: {:?}", result),
+    }
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn manager_rejects_missing_and_protected_databases() {
+    let fixture = Fixture::setup();
+    let manager = fixture.manager();
+    let server = fixture.cluster.host();
+
+    runtime().block_on(async {
+        match manager
+            .plan(server, &fixture.database("missing"), Some("dba"), "127.0.0.1")
+            .await
+        {
+            Err(ManagementError::DatabaseNotFound { .. }) => {}
+            result => panic!("Unexpected plan result: {:?}", result),
+        }
+
+        match manager
+            .plan(server, &fixture.metadata_database(), Some("dba"), "127.0.0.1")
+            .await
+        {
+            Err(ManagementError::ProtectedDatabase { .. }) => {}
+            result => panic!("Unexpected plan result: {:?}", result),
+        }
+    });
+
+    assert!(fixture.database_exists("metadata"));
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn metadata_query_ignores_invalid_created_column() {
+    let fixture = Fixture::setup();
+    let json = fixture.metadata_json(
+        "SELECT commit, branch, project, 'yesterday'::text AS created FROM monitor \
+         WHERE server_name = $1 AND database_name = $2",
+    );
+    let connection_info: MetadataConnInfo = serde_json::from_value(json).unwrap();
+    let databases = vec![ServerDatabase::new(
+        fixture.cluster.host(),
+        &fixture.database("owned"),
+    )];
+    let metadata = runtime()
+        .block_on(connector().query_metadata(&connection_info, &databases))
+        .unwrap();
+
+    assert_eq!(metadata.len(), 1);
+    assert_eq!(metadata[0].commit(), 1024);
+    assert_eq!(metadata[0].created(), None);
+}
+
+#[test]
+#[ignore = "requires PostgreSQL"]
+fn probe_skips_replicas_hidden_from_monitoring_role() {
+    let fixture = Fixture::setup();
+    let schema = fixture.database("replicas");
+    let role = fixture.database("monitor");
+    let mut conn = fixture.cluster.connect("postgres");
+
+    conn.batch_execute(&format!(
+        "CREATE ROLE {1} LOGIN PASSWORD 'monitor';
+         ALTER ROLE {1} SET search_path = {0}, pg_catalog;
+         CREATE SCHEMA {0};
+         GRANT USAGE ON SCHEMA {0} TO {1};
+         CREATE VIEW {0}.pg_stat_replication AS
+             SELECT 1 AS pid, NULL::text AS application_name, NULL::inet AS client_addr,
+                 NULL::text AS client_hostname, NULL::text AS state, NULL::pg_lsn AS replay_lsn
+             UNION ALL
+             SELECT 2, 'standby', '10.0.0.2'::inet, NULL, 'streaming', '0/0'::pg_lsn;
+         GRANT SELECT ON {0}.pg_stat_replication TO {1};",
+        schema, role
+    ))
+    .expect("Failed to create replication view");
+
+    let json = serde_json::json!({
+        "host": fixture.cluster.host(),
+        "port": fixture.cluster.port(),
+        "role": role,
+        "password": "monitor",
+    });
+    let connection_info: ServerConnInfo = serde_json::from_value(json).unwrap();
+    let (details, _) = runtime().block_on(probe_server(&connection_info, Duration::from_secs(TIMEOUT))).unwrap();
+    let replicas = details.replicas();
+
+    assert_eq!(replicas.len(), 1);
+    assert_eq!(replicas[0].application_name(), "standby");
+    assert_eq!(replicas[0].client_addr(), &Some("10.0.0.2".to_string()));
+    assert_eq!(replicas[0].state(), "streaming");
+}
@@ -0,0 +1,41 @@
+use std::io::Read;
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use tokio::time;
+
+use database_monitor::config::ServerConnInfo;
+use database_monitor::worker::probe_server;
+
+#[tokio::test]
+async fn mysql_probe_gives_up_on_silent_server() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buffer = Vec::new();
+            let _ = stream.read_to_end(&mut buffer);
+        }
+    });
+
+    let json = serde_json::json!({
+        "kind": "mysql",
+        "host": "127.0.0.1",
+        "port": port,
+        "role": "monitor",
+        "password": "secret",
+    });
+    let connection_info: ServerConnInfo = serde_json::from_value(json).unwrap();
+    let started = Instant::now();
+    let probe = probe_server(&connection_info, Duration::from_secs(1));
+    let result = time::timeout(Duration::from_secs(10), probe)
+        .await
+        .expect("Probe did not give up on its own");
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,21 @@
+use database_monitor::config::RetentionRule;
+use database_monitor::retention::RetentionPolicy;
+use database_monitor::state::DatabaseInfo;
+
+fn policy(rules: &str) -> RetentionPolicy {
+    let rules: Vec<RetentionRule> = serde_json::from_str(rules).unwrap();
+
+    RetentionPolicy::new(&rules)
+}
+
+#[test]
+fn owner_rules_skip_databases_without_owner() {
+    let policy = policy(r#"[ { "flag": "ci", "owner": "ci" }, { "flag": "any", "owner": "" } ]"#);
+    let owned = DatabaseInfo::new("alpha", "owned", "C", "ci", 0);
+    let other = DatabaseInfo::new("alpha", "other", "C", "app", 0);
+    let mysql = DatabaseInfo::new("mysql", "schema", "utf8mb4_general_ci", "", 0);
+
+    assert_eq!(policy.flags(&owned, 0), vec!["ci".to_string()]);
+    assert!(policy.flags(&other, 0).is_empty());
+    assert!(policy.flags(&mysql, 0).is_empty());
+}
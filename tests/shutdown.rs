@@ -0,0 +1,177 @@
+mod common;
+
+use std::convert::Infallible;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::process::Command;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::middleware;
+use axum::routing::get;
+use axum::Router;
+use futures::stream;
+use futures::StreamExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::time;
+
+use database_monitor::handlers::DrainHandler;
+use database_monitor::shutdown::Shutdown;
+
+use common::free_port;
+use common::wait_for_port;
+use common::Monitor;
+
+fn shutdown_config(port: u16, server_port: u16) -> String {
+    format!(
+        r#"{{
+            "address": "127.0.0.1",
+            "port": {},
+            "interval": 600,
+            "servers": [
+                {{
+                    "host": "127.0.0.1",
+                    "port": {},
+                    "role": "postgres",
+                    "password": "postgres"
+                }}
+            ]
+        }}"#,
+        port, server_port
+    )
+}
+
+fn send_signal(monitor: &Monitor, signal: &str) {
+    let status = Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg(monitor.id().to_string())
+        .status()
+        .expect("Failed to send signal");
+
+    assert!(status.success());
+}
+
+fn assert_exits_cleanly(monitor: &mut Monitor, signal: &str) {
+    let start = Instant::now();
+
+    while start.elapsed() < Duration::from_secs(15) {
+        if let Some(status) = monitor.child_mut().try_wait().expect("Failed to wait") {
+            assert!(status.success(), "Monitor exited with {}", status);
+
+            return;
+        }
+
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    panic!("Monitor did not stop after SIG{}", signal);
+}
+
+fn assert_stops_on(signal: &str) {
+    let port = free_port();
+    let mut monitor = Monitor::start(
+        &format!("shutdown-{}", signal),
+        &shutdown_config(port, free_port()),
+    );
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    send_signal(&monitor, signal);
+    assert_exits_cleanly(&mut monitor, signal);
+}
+
+#[test]
+fn stops_on_sigterm() {
+    assert_stops_on("TERM");
+}
+
+#[test]
+fn stops_on_sigint() {
+    assert_stops_on("INT");
+}
+
+#[test]
+fn open_event_stream_is_closed_on_shutdown() {
+    let port = free_port();
+    let mut monitor = Monitor::start("shutdown-events", &shutdown_config(port, free_port()));
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("Failed to connect");
+    stream
+        .write_all(b"GET /api/v1/events HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n")
+        .expect("Failed to send request");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(15)))
+        .expect("Failed to set timeout");
+
+    let mut head = [0; 12];
+    stream.read_exact(&mut head).expect("Failed to read status");
+
+    assert_eq!(&head, b"HTTP/1.1 200");
+
+    send_signal(&monitor, "TERM");
+
+    let mut rest = Vec::new();
+    stream
+        .read_to_end(&mut rest)
+        .expect("Event stream was not closed on shutdown");
+
+    assert!(String::from_utf8_lossy(&rest).ends_with("0\r\n\r\n"));
+    assert_exits_cleanly(&mut monitor, "TERM");
+}
+
+async fn slow_stream() -> Body {
+    let first = stream::once(async { Ok::<_, Infallible>("first") });
+    let second = stream::once(async {
+        time::sleep(Duration::from_secs(1)).await;
+
+        Ok("second")
+    });
+
+    Body::from_stream(first.chain(second))
+}
+
+#[tokio::test]
+async fn streamed_response_is_in_flight_until_body_ends() {
+    let shutdown = Shutdown::default();
+    let router = Router::new()
+        .route("/stream", get(slow_stream))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(DrainHandler::new(shutdown.clone())),
+            DrainHandler::handle,
+        ));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    tokio::spawn(async move { axum::serve(listener, router).await });
+
+    let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+    let mut received = Vec::new();
+
+    stream
+        .write_all(b"GET /stream HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    while !String::from_utf8_lossy(&received).contains("first") {
+        let mut buffer = [0; 1024];
+        let size = stream.read(&mut buffer).await.unwrap();
+
+        assert!(size > 0, "Connection closed before the first chunk");
+        received.extend_from_slice(&buffer[..size]);
+    }
+
+    assert!(!shutdown.wait_idle(Duration::from_millis(0)).await);
+
+    stream.read_to_end(&mut received).await.unwrap();
+
+    assert!(String::from_utf8_lossy(&received).contains("second"));
+    assert!(shutdown.wait_idle(Duration::from_secs(5)).await);
+}
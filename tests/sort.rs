@@ -0,0 +1,36 @@
+use std::cmp::Ordering;
+
+use database_monitor::handlers::DatabasesHandler;
+use database_monitor::state::DatabaseRow;
+
+fn row(server_name: &str, database_name: &str, weight: usize) -> DatabaseRow {
+    DatabaseRow::new(server_name, &None, database_name, "C", "owner").with_weight(weight)
+}
+
+#[test]
+fn compare_databases_is_antisymmetric() {
+    let rows = vec![
+        row("alpha", "app", 1),
+        row("beta", "app", 1),
+        row("alpha", "other", 1),
+        row("beta", "app", 2),
+    ];
+
+    for a in &rows {
+        for b in &rows {
+            let forward = DatabasesHandler::compare_databases(a, b);
+            let backward = DatabasesHandler::compare_databases(b, a);
+            assert_eq!(forward, backward.reverse());
+            assert_eq!(forward == Ordering::Equal, a == b);
+        }
+    }
+}
+
+#[test]
+fn compare_databases_breaks_ties_by_server_name() {
+    let alpha = row("alpha", "app", 1);
+    let beta = row("beta", "app", 1);
+
+    assert_eq!(DatabasesHandler::compare_databases(&alpha, &beta), Ordering::Greater);
+    assert_eq!(DatabasesHandler::compare_databases(&beta, &alpha), Ordering::Less);
+}
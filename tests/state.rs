@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::process;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+
+use database_monitor::config::RetentionRule;
+use database_monitor::retention::RetentionPolicy;
+use database_monitor::state::DatabaseInfo;
+use database_monitor::state::SavedDatabase;
+use database_monitor::state::SavedDatabases;
+use database_monitor::state::ServerDetails;
+use database_monitor::state::State;
+use database_monitor::worker::DatabaseMetadata;
+
+const UPDATES: usize = 200;
+
+fn set_databases(state: &State, server_name: &str, names: &[String]) {
+    set_databases_with_backends(state, server_name, names, 0);
+}
+
+fn set_databases_with_backends(state: &State, server_name: &str, names: &[String], backends: i64) {
+    let details = ServerDetails::new("fake", 0, false, None, 100, Vec::new());
+    let databases = names
+        .iter()
+        .map(|name| DatabaseInfo::new(server_name, name, "C", "owner", backends))
+        .collect();
+
+    state.update_server(server_name, &None, details, databases);
+}
+
+fn names(prefix: &str, count: usize) -> Vec<String> {
+    (0..count)
+        .map(|index| format!("{}{}", prefix, index))
+        .collect()
+}
+
+fn server_databases(state: &State, server_name: &str) -> Vec<String> {
+    let mut names: Vec<String> = state
+        .databases()
+        .iter()
+        .filter(|row| row.server_name() == server_name)
+        .map(|row| row.database_name().clone())
+        .collect();
+    names.sort();
+
+    names
+}
+
+fn first_seen(state: &State, server_name: &str) -> HashMap<String, i64> {
+    state
+        .databases()
+        .iter()
+        .filter(|row| row.server_name() == server_name)
+        .map(|row| (row.database_name().clone(), row.first_seen()))
+        .collect()
+}
+
+#[test]
+fn readers_never_see_partial_server_updates() {
+    let state = State::default();
+    let mut small = names("small", 10);
+    let mut large = names("large", 50);
+
+    small.sort();
+    large.sort();
+    set_databases(&state, "alpha", &small);
+
+    let done = Arc::new(AtomicBool::new(false));
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let state = state.clone();
+            let done = done.clone();
+            let small = small.clone();
+            let large = large.clone();
+
+            thread::spawn(move || {
+                while !done.load(Ordering::SeqCst) {
+                    let seen = server_databases(&state, "alpha");
+
+                    assert!(seen == small || seen == large, "partial update: {:?}", seen);
+                }
+            })
+        })
+        .collect();
+
+    for index in 0..UPDATES {
+        let names = if index % 2 == 0 { &large } else { &small };
+
+        set_databases(&state, "alpha", names);
+        set_databases(&state, "beta", names);
+    }
+
+    done.store(true, Ordering::SeqCst);
+
+    for reader in readers {
+        reader.join().expect("Reader saw an inconsistent snapshot");
+    }
+}
+
+#[test]
+fn metadata_is_applied_in_one_update() {
+    let state = State::default();
+
+    set_databases(&state, "alpha", &names("db", 3));
+    set_databases(&state, "beta", &names("db", 1));
+
+    let last_update = state.last_update();
+
+    state.set_databases_metadata(&[
+        DatabaseMetadata::new("alpha", "db0", 10, "master", "project", None),
+        DatabaseMetadata::new("alpha", "db2", 20, "feature", "project", None),
+        DatabaseMetadata::new("alpha", "missing", 30, "master", "project", None),
+        DatabaseMetadata::new("gamma", "db0", 40, "master", "project", None),
+    ]);
+
+    let mut commits: Vec<(String, String, Option<i64>)> = state
+        .databases()
+        .iter()
+        .map(|row| {
+            (
+                row.server_name().clone(),
+                row.database_name().clone(),
+                *row.commit(),
+            )
+        })
+        .collect();
+    commits.sort();
+
+    assert_eq!(
+        commits,
+        vec![
+            ("alpha".into(), "db0".into(), Some(10)),
+            ("alpha".into(), "db1".into(), None),
+            ("alpha".into(), "db2".into(), Some(20)),
+            ("beta".into(), "db0".into(), None),
+        ]
+    );
+    assert_eq!(state.last_update(), last_update);
+
+    state.remove_database("alpha", "db1");
+    state.remove_database("alpha", "missing");
+
+    assert_eq!(server_databases(&state, "alpha"), vec!["db0", "db2"]);
+    assert_eq!(server_databases(&state, "beta"), vec!["db0"]);
+}
+
+fn state_path(name: &str) -> String {
+    env::temp_dir()
+        .join(format!("database-monitor-{}-{}.json", name, process::id()))
+        .display()
+        .to_string()
+}
+
+fn read_state_file(path: &str) -> SavedDatabases {
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn first_seen_is_saved_to_state_file() {
+    let path = state_path("first-seen");
+    let state_file = Some(path.clone());
+
+    fs::write(
+        &path,
+        r#"{
+            "alpha": {
+                "kept": { "first_seen": 1000, "last_active": 1500 },
+                "gone": { "first_seen": 2000, "last_active": 2000 }
+            },
+            "beta": { "idle": { "first_seen": 3000, "last_active": 3000 } }
+        }"#,
+    )
+    .unwrap();
+
+    let state = State::open(RetentionPolicy::default(), &state_file).unwrap();
+
+    set_databases(&state, "alpha", &["kept".to_string(), "new".to_string()]);
+
+    let seen = first_seen(&state, "alpha");
+
+    assert_eq!(seen["kept"], 1000);
+    assert!(seen["new"] > 1000);
+
+    let saved = read_state_file(&path);
+    let saved_first_seen: HashMap<String, i64> = saved["alpha"]
+        .iter()
+        .map(|(name, database)| (name.clone(), database.first_seen()))
+        .collect();
+
+    assert_eq!(saved_first_seen, seen);
+    assert_eq!(saved["beta"]["idle"], SavedDatabase::new(3000, 3000));
+
+    let state = State::open(RetentionPolicy::default(), &state_file).unwrap();
+
+    set_databases(&state, "alpha", &["new".to_string()]);
+
+    assert_eq!(first_seen(&state, "alpha")["new"], seen["new"]);
+
+    state.remove_database("alpha", "new");
+
+    assert!(read_state_file(&path)["alpha"].is_empty());
+
+    fs::write(&path, "not json").unwrap();
+
+    assert!(State::open(RetentionPolicy::default(), &state_file).is_err());
+
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn last_active_survives_restart() {
+    let path = state_path("last-active");
+    let state_file = Some(path.clone());
+    let rules: Vec<RetentionRule> =
+        serde_json::from_str(r#"[ { "flag": "idle", "idle_days": 1 } ]"#).unwrap();
+    let idle_since = time::get_time().sec - 2 * 24 * 60 * 60;
+
+    fs::write(
+        &path,
+        format!(
+            r#"{{ "alpha": {{ "idle": {{ "first_seen": {0}, "last_active": {0} }} }} }}"#,
+            idle_since
+        ),
+    )
+    .unwrap();
+
+    let state = State::open(RetentionPolicy::new(&rules), &state_file).unwrap();
+
+    set_databases(&state, "alpha", &["idle".to_string(), "new".to_string()]);
+
+    let flags = |state: &State| -> HashMap<String, Vec<String>> {
+        state
+            .databases()
+            .iter()
+            .map(|row| (row.database_name().clone(), row.flags().clone()))
+            .collect()
+    };
+
+    assert_eq!(flags(&state)["idle"], vec!["idle".to_string()]);
+    assert!(flags(&state)["new"].is_empty());
+
+    let saved = read_state_file(&path);
+
+    assert_eq!(saved["alpha"]["idle"].last_active(), idle_since);
+    assert!(saved["alpha"]["new"].last_active() > idle_since);
+
+    set_databases_with_backends(&state, "alpha", &["idle".to_string()], 1);
+
+    let active = read_state_file(&path)["alpha"]["idle"].last_active();
+
+    assert!(active > idle_since);
+
+    let state = State::open(RetentionPolicy::new(&rules), &state_file).unwrap();
+
+    set_databases(&state, "alpha", &["idle".to_string()]);
+
+    assert!(flags(&state)["idle"].is_empty());
+    assert_eq!(read_state_file(&path)["alpha"]["idle"].last_active(), active);
+
+    let _ = fs::remove_file(&path);
+}
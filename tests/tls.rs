@@ -0,0 +1,197 @@
+mod common;
+
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use openssl::ssl::SslConnector;
+use openssl::ssl::SslMethod;
+use openssl::ssl::SslVerifyMode;
+
+use common::free_port;
+use common::project_path;
+use common::wait_for_port;
+use common::Monitor;
+
+fn tls_config(port: u16, redirect_port: u16, tls_fields: &str, fields: &str) -> String {
+    format!(
+        r#"{{
+            "address": "127.0.0.1",
+            "port": {},
+            "interval": 600,
+            "servers": [],
+            "tls": {{
+                "certificate": "{}",
+                "key": "{}",
+                "redirect_port": {}{}
+            }}{}
+        }}"#,
+        port,
+        project_path("tests/tls/certificate.pem").display(),
+        project_path("tests/tls/key.pem").display(),
+        redirect_port,
+        tls_fields,
+        fields
+    )
+}
+
+fn trusted_proxies(address: &str) -> String {
+    format!(r#", "trusted_proxies": [ "{}" ]"#, address)
+}
+
+fn redirect(redirect_port: u16, headers: &str) -> String {
+    let mut stream = TcpStream::connect(("127.0.0.1", redirect_port)).unwrap();
+    let request = format!(
+        "GET /servers HTTP/1.1\r\nHost: localhost:{}\r\n{}Connection: close\r\n\r\n",
+        redirect_port, headers
+    );
+
+    stream.write_all(request.as_bytes()).unwrap();
+
+    read_response(&mut stream)
+}
+
+fn read_response(stream: &mut dyn Read) -> String {
+    let mut response = String::new();
+    let _ = stream.read_to_string(&mut response);
+
+    response
+}
+
+#[test]
+fn serves_api_over_https() {
+    let port = free_port();
+    let redirect_port = free_port();
+    let _monitor = Monitor::start("tls", &tls_config(port, redirect_port, "", ""));
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    let mut builder = SslConnector::builder(SslMethod::tls()).unwrap();
+    builder.set_verify(SslVerifyMode::NONE);
+
+    let connector = builder.build();
+    let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    let mut stream = connector.connect("localhost", stream).unwrap();
+    let certificate = stream.ssl().peer_certificate().unwrap();
+    let common_name = certificate
+        .subject_name()
+        .entries()
+        .next()
+        .unwrap()
+        .data()
+        .to_string()
+        .unwrap();
+
+    assert_eq!(common_name, "localhost");
+
+    stream
+        .write_all(b"GET /api/v1/status HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .unwrap();
+
+    let response = read_response(&mut stream);
+
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(response.contains("\"ok\":true"), "{}", response);
+}
+
+#[test]
+fn rejects_plain_http_on_https_port() {
+    let port = free_port();
+    let redirect_port = free_port();
+    let _monitor = Monitor::start("tls-plain", &tls_config(port, redirect_port, "", ""));
+
+    assert!(wait_for_port(port, Duration::from_secs(10)));
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    stream
+        .write_all(b"GET /api/v1/status HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .unwrap();
+
+    let response = read_response(&mut stream);
+
+    assert!(!response.contains("\"ok\":true"), "{}", response);
+}
+
+#[test]
+fn redirects_plain_http_to_https() {
+    let port = free_port();
+    let redirect_port = free_port();
+    let _monitor = Monitor::start("tls-redirect", &tls_config(port, redirect_port, "", ""));
+
+    assert!(wait_for_port(redirect_port, Duration::from_secs(10)));
+
+    let mut stream = TcpStream::connect(("127.0.0.1", redirect_port)).unwrap();
+    let request = format!(
+        "GET /api/v1/status?x=1 HTTP/1.1\r\nHost: localhost:{}\r\nConnection: close\r\n\r\n",
+        redirect_port
+    );
+
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let response = read_response(&mut stream);
+    let location = format!("location: https://localhost:{}/api/v1/status?x=1", port);
+
+    assert!(response.starts_with("HTTP/1.1 301"), "{}", response);
+    assert!(response.to_lowercase().contains(&location), "{}", response);
+}
+
+#[test]
+fn redirect_honours_forwarded_headers_of_trusted_proxy() {
+    let port = free_port();
+    let redirect_port = free_port();
+    let config = tls_config(port, redirect_port, "", &trusted_proxies("127.0.0.1"));
+    let _monitor = Monitor::start("tls-forwarded", &config);
+
+    assert!(wait_for_port(redirect_port, Duration::from_secs(10)));
+
+    let response = redirect(
+        redirect_port,
+        "X-Forwarded-Host: tools.example.com\r\nX-Forwarded-Prefix: /dbmon\r\n\
+         X-Forwarded-Proto: http\r\n",
+    );
+
+    assert!(response.starts_with("HTTP/1.1 301"), "{}", response);
+    assert!(
+        response
+            .to_lowercase()
+            .contains("location: https://tools.example.com/dbmon/servers"),
+        "{}",
+        response
+    );
+
+    let response = redirect(
+        redirect_port,
+        "X-Forwarded-Host: tools.example.com\r\nX-Forwarded-Proto: https\r\n",
+    );
+
+    assert!(response.starts_with("HTTP/1.1 400"), "{}", response);
+}
+
+#[test]
+fn redirect_ignores_forwarded_headers_of_untrusted_clients() {
+    let port = free_port();
+    let redirect_port = free_port();
+    let config = tls_config(
+        port,
+        redirect_port,
+        r#", "redirect_host": "dbmon.example.com""#,
+        &trusted_proxies("127.0.0.2"),
+    );
+    let _monitor = Monitor::start("tls-untrusted", &config);
+
+    assert!(wait_for_port(redirect_port, Duration::from_secs(10)));
+
+    let response = redirect(
+        redirect_port,
+        "X-Forwarded-Host: evil.example.com\r\nX-Forwarded-Prefix: /evil\r\n\
+         X-Forwarded-Proto: https\r\n",
+    );
+    let location = format!("location: https://dbmon.example.com:{}/servers", port);
+
+    assert!(response.starts_with("HTTP/1.1 301"), "{}", response);
+    assert!(response.to_lowercase().contains(&location), "{}", response);
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::process::Command;
+use std::process::Output;
+
+use openssl::sha::sha256;
+
+use common::project_path;
+
+const FONTS: &str = "themes/default/assets/fonts";
+const FILES: &[(&str, &str)] = &[
+    ("require.js/2.3.5/require.min.js", "require.min.js"),
+    ("knockout/3.4.2/knockout-min.js", "knockout-min.js"),
+    ("moment.js/2.19.1/moment.min.js", "moment.min.js"),
+    ("reqwest/2.0.5/reqwest.min.js", "reqwest.min.js"),
+    ("semantic-ui/2.2.7/semantic.css", "semantic-ui/semantic.css"),
+];
+const FONT_FILES: &[&str] = &["icons.eot", "icons.svg", "icons.ttf", "icons.woff", "icons.woff2"];
+
+struct Mirror {
+    root: PathBuf,
+}
+
+impl Mirror {
+    fn new(name: &str) -> Mirror {
+        let root = env::temp_dir().join(format!("database-monitor-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        Mirror { root }
+    }
+
+    fn files() -> Vec<(String, String)> {
+        let mut files: Vec<(String, String)> = FILES
+            .iter()
+            .map(|(source, target)| (source.to_string(), target.to_string()))
+            .collect();
+
+        for font in FONT_FILES {
+            files.push((
+                format!("semantic-ui/2.2.7/{}/{}", FONTS, font),
+                format!("semantic-ui/{}/{}", FONTS, font),
+            ));
+        }
+
+        files
+    }
+
+    fn publish(&self) -> Vec<(String, String)> {
+        Mirror::files()
+            .into_iter()
+            .map(|(source, target)| {
+                let path = self.root.join("cdn").join(&source);
+                let content = format!("content of {}", target);
+
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, &content).unwrap();
+
+                let hash: String = sha256(content.as_bytes())
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+
+                (target, hash)
+            })
+            .collect()
+    }
+
+    fn vendor_dir(&self) -> PathBuf {
+        self.root.join("vendor")
+    }
+
+    fn run(&self, checksums: &[(String, String)]) -> Output {
+        let checksum_file = self.root.join("vendor.sha256");
+        let lines: Vec<String> = checksums
+            .iter()
+            .map(|(file, hash)| format!("{}  {}\n", hash, file))
+            .collect();
+
+        fs::write(&checksum_file, lines.concat()).unwrap();
+
+        Command::new("sh")
+            .arg(project_path("scripts/vendor.sh"))
+            .env("DBMON_VENDOR_CDN", format!("file://{}", self.root.join("cdn").display()))
+            .env("DBMON_VENDOR_DIR", self.vendor_dir())
+            .env("DBMON_VENDOR_CHECKSUMS", checksum_file)
+            .output()
+            .expect("Failed to run vendor.sh")
+    }
+}
+
+impl Drop for Mirror {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn assert_installed(vendor_dir: &Path) {
+    for (_, target) in Mirror::files() {
+        assert!(vendor_dir.join(&target).is_file(), "{} is not installed", target);
+    }
+}
+
+#[test]
+fn vendor_script_installs_verified_files() {
+    let mirror = Mirror::new("vendor-verified");
+    let checksums = mirror.publish();
+    let output = mirror.run(&checksums);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_installed(&mirror.vendor_dir());
+    assert_eq!(
+        fs::read_to_string(mirror.vendor_dir().join("knockout-min.js")).unwrap(),
+        "content of knockout-min.js"
+    );
+}
+
+#[test]
+fn vendor_script_rejects_unknown_and_modified_files() {
+    let mirror = Mirror::new("vendor-rejected");
+    let mut checksums = mirror.publish();
+
+    checksums.retain(|(file, _)| file != "moment.min.js");
+    checksums
+        .iter_mut()
+        .filter(|(file, _)| file == "knockout-min.js")
+        .for_each(|(_, hash)| *hash = "0".repeat(64));
+
+    let output = mirror.run(&checksums);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("No checksum for moment.min.js"), "{}", stderr);
+    assert!(stderr.contains("Checksum mismatch for knockout-min.js"), "{}", stderr);
+    assert!(!mirror.vendor_dir().exists());
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn vendor_script_downloads_pinned_libraries() {
+    let mirror = Mirror::new("vendor-download");
+    let output = Command::new("sh")
+        .arg(project_path("scripts/vendor.sh"))
+        .env("DBMON_VENDOR_DIR", mirror.vendor_dir())
+        .output()
+        .expect("Failed to run vendor.sh");
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_installed(&mirror.vendor_dir());
+}
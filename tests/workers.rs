@@ -0,0 +1,401 @@
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast::Receiver;
+use tokio::time;
+
+use database_monitor_api::Event;
+use database_monitor::config::MetadataConnInfo;
+use database_monitor::config::ServerConnInfo;
+use database_monitor::state::DatabaseInfo;
+use database_monitor::state::ServerDetails;
+use database_monitor::state::State;
+use database_monitor::worker::DatabaseMetadata;
+use database_monitor::worker::DatabaseWorker;
+use database_monitor::worker::MetadataWorker;
+use database_monitor::worker::ServerDatabase;
+use database_monitor::worker::WorkerError;
+use database_monitor::worker::WorkerMessage;
+
+use common::wait_until_async;
+use common::FakeConnector;
+
+const TIMEOUT: u64 = 5;
+
+fn server(name: &str) -> ServerConnInfo {
+    let json = format!(
+        r#"{{ "host": "{}", "role": "user", "password": "password" }}"#,
+        name
+    );
+
+    serde_json::from_str(&json).unwrap()
+}
+
+fn metadata_config() -> MetadataConnInfo {
+    serde_json::from_str(
+        r#"{
+            "host": "metadata",
+            "database": "metadata",
+            "role": "metadata",
+            "password": "metadata",
+            "query": "SELECT 1"
+        }"#,
+    )
+    .unwrap()
+}
+
+fn metadata(server_name: &str, database_name: &str, commit: i64) -> DatabaseMetadata {
+    DatabaseMetadata::new(
+        server_name,
+        database_name,
+        commit,
+        "master",
+        "project",
+        None,
+    )
+}
+
+fn database_names(state: &State) -> Vec<String> {
+    let mut names: Vec<String> = state
+        .databases()
+        .iter()
+        .map(|row| row.database_name().clone())
+        .collect();
+    names.sort();
+
+    names
+}
+
+fn set_databases(state: &State, server_name: &str, names: &[&str]) {
+    let details = ServerDetails::new("fake", 0, false, None, 100, Vec::new());
+    let databases = names
+        .iter()
+        .map(|name| DatabaseInfo::new(server_name, name, "C", "owner", 0))
+        .collect();
+
+    state.update_server(server_name, &None, details, databases);
+}
+
+fn commit_of(state: &State, database_name: &str) -> Option<i64> {
+    state
+        .databases()
+        .iter()
+        .find(|row| row.database_name() == database_name)
+        .and_then(|row| *row.commit())
+}
+
+async fn wait<F>(condition: F) -> bool
+where
+    F: FnMut() -> bool,
+{
+    wait_until_async(Duration::from_secs(TIMEOUT), condition).await
+}
+
+async fn next_event(receiver: &mut Receiver<Event>) -> Event {
+    time::timeout(Duration::from_secs(TIMEOUT), receiver.recv())
+        .await
+        .expect("Timed out waiting for event")
+        .expect("Event channel closed")
+}
+
+fn spawn_database_worker(connector: &Arc<FakeConnector>, state: &State) -> DatabaseWorker {
+    DatabaseWorker::spawn(
+        vec![server("alpha")],
+        600,
+        30,
+        state.clone(),
+        connector.clone(),
+        None,
+    )
+}
+
+fn spawn_metadata_worker(connector: &Arc<FakeConnector>, state: &State) -> MetadataWorker {
+    MetadataWorker::spawn(metadata_config(), 600, state.clone(), connector.clone())
+}
+
+#[tokio::test]
+async fn database_worker_removes_vanished_databases() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+
+    connector.script_databases("alpha", &["first", "second"]);
+    connector.script_databases("alpha", &["first"]);
+
+    let worker = spawn_database_worker(&connector, &state);
+
+    assert!(wait(|| database_names(&state) == vec!["first", "second"]).await);
+
+    worker.sender().send(WorkerMessage::RefreshAll).unwrap();
+
+    assert!(wait(|| database_names(&state) == vec!["first"]).await);
+    assert_eq!(connector.probes(), vec!["alpha", "alpha"]);
+
+    worker.shutdown();
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+}
+
+#[tokio::test]
+async fn database_worker_keeps_databases_on_probe_error() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+
+    connector.script_databases("alpha", &["first"]);
+    connector.script_probe_error(
+        "alpha",
+        WorkerError::ConnectError {
+            message: "connection refused".into(),
+        },
+    );
+    connector.script_databases("alpha", &["first", "second"]);
+
+    let worker = spawn_database_worker(&connector, &state);
+
+    assert!(wait(|| database_names(&state) == vec!["first"]).await);
+
+    worker.sender().send(WorkerMessage::RefreshAll).unwrap();
+
+    assert!(wait(|| connector.probes().len() == 2).await);
+    assert_eq!(database_names(&state), vec!["first"]);
+
+    worker
+        .sender()
+        .send(WorkerMessage::RefreshServer("alpha".into()))
+        .unwrap();
+
+    assert!(wait(|| database_names(&state) == vec!["first", "second"]).await);
+
+    worker.shutdown();
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+}
+
+#[tokio::test]
+async fn metadata_worker_skips_ignored_databases() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+
+    set_databases(&state, "alpha", &["first", "second"]);
+    connector.script_metadata(vec![metadata("alpha", "first", 10)]);
+    connector.script_metadata(Vec::new());
+
+    let worker = spawn_metadata_worker(&connector, &state);
+
+    assert!(wait(|| commit_of(&state, "first") == Some(10)).await);
+
+    let requests = connector.metadata_requests();
+
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].contains(&ServerDatabase::new("alpha", "first")));
+    assert!(requests[0].contains(&ServerDatabase::new("alpha", "second")));
+
+    set_databases(&state, "alpha", &["first", "second", "third"]);
+    worker.sender().send(WorkerMessage::RefreshAll).unwrap();
+
+    assert!(wait(|| connector.metadata_requests().len() == 2).await);
+    assert_eq!(
+        connector.metadata_requests()[1],
+        vec![ServerDatabase::new("alpha", "third")]
+    );
+    assert_eq!(commit_of(&state, "second"), None);
+
+    worker.shutdown();
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+}
+
+#[tokio::test]
+async fn metadata_worker_continues_after_error() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+
+    set_databases(&state, "alpha", &["first"]);
+    connector.script_metadata_error(WorkerError::DatabaseError {
+        code: "42P01".into(),
+        message: "relation \"monitor\" does not exist".into(),
+    });
+    connector.script_metadata(vec![metadata("alpha", "second", 20)]);
+
+    let worker = spawn_metadata_worker(&connector, &state);
+
+    assert!(wait(|| connector.metadata_requests().len() == 1).await);
+    assert_eq!(commit_of(&state, "first"), None);
+
+    set_databases(&state, "alpha", &["first", "second"]);
+    worker.sender().send(WorkerMessage::RefreshAll).unwrap();
+
+    assert!(wait(|| commit_of(&state, "second") == Some(20)).await);
+
+    let requests = connector.metadata_requests();
+
+    assert_eq!(requests[1].len(), 2);
+    assert!(requests[1].contains(&ServerDatabase::new("alpha", "first")));
+    assert!(requests[1].contains(&ServerDatabase::new("alpha", "second")));
+
+    worker.shutdown();
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+}
+
+#[tokio::test]
+async fn database_worker_publishes_events() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+    let mut events = state.subscribe();
+
+    connector.script_databases("alpha", &["first", "second"]);
+    connector.script_probe_error(
+        "alpha",
+        WorkerError::ConnectError {
+            message: "connection refused".into(),
+        },
+    );
+    connector.script_databases("alpha", &["first", "third"]);
+
+    let worker = spawn_database_worker(&connector, &state);
+
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::server_health("alpha", true, None)
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::refresh_finished(state.last_update())
+    );
+
+    worker.sender().send(WorkerMessage::RefreshAll).unwrap();
+
+    match next_event(&mut events).await {
+        Event::ServerHealth {
+            server_name,
+            healthy,
+            message,
+        } => {
+            assert_eq!(server_name, "alpha");
+            assert!(!healthy);
+            assert!(message.unwrap().contains("connection refused"));
+        }
+        event => panic!("Unexpected event {:?}", event),
+    }
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::RefreshFinished { .. }
+    ));
+
+    worker
+        .sender()
+        .send(WorkerMessage::RefreshServer("alpha".into()))
+        .unwrap();
+
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::database_created("alpha", "third")
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::database_dropped("alpha", "second")
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::server_health("alpha", true, None)
+    );
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::RefreshFinished { .. }
+    ));
+
+    state.remove_database("alpha", "first");
+
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::database_dropped("alpha", "first")
+    );
+
+    worker.shutdown();
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+}
+
+#[tokio::test]
+async fn database_worker_times_out_slow_probes() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+    let mut events = state.subscribe();
+
+    connector.script_databases("alpha", &["first"]);
+    connector.script_databases("beta", &["second"]);
+    connector.script_probe_delay("beta", Duration::from_secs(600));
+
+    let worker = DatabaseWorker::spawn(
+        vec![server("alpha"), server("beta")],
+        600,
+        1,
+        state.clone(),
+        connector.clone(),
+        None,
+    );
+
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::server_health("alpha", true, None)
+    );
+    assert_eq!(
+        next_event(&mut events).await,
+        Event::server_health(
+            "beta",
+            false,
+            Some("Probe timed out after 1 seconds".into())
+        )
+    );
+    assert!(matches!(
+        next_event(&mut events).await,
+        Event::RefreshFinished { .. }
+    ));
+    assert_eq!(database_names(&state), vec!["first"]);
+
+    worker.shutdown();
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+}
+
+#[tokio::test]
+async fn database_worker_cancels_probes_on_shutdown() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+    let mut events = state.subscribe();
+
+    connector.script_databases("alpha", &["first"]);
+    connector.script_probe_delay("alpha", Duration::from_secs(600));
+
+    let worker = DatabaseWorker::spawn(
+        vec![server("alpha")],
+        600,
+        600,
+        state.clone(),
+        connector.clone(),
+        None,
+    );
+
+    assert!(wait(|| connector.probes() == vec!["alpha"]).await);
+
+    worker.shutdown();
+
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+    assert!(database_names(&state).is_empty());
+    assert!(events.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn metadata_worker_cancels_query_on_shutdown() {
+    let connector = Arc::new(FakeConnector::new());
+    let state = State::default();
+
+    set_databases(&state, "alpha", &["first"]);
+    connector.script_metadata(vec![metadata("alpha", "first", 10)]);
+    connector.script_metadata_delay(Duration::from_secs(600));
+
+    let worker = spawn_metadata_worker(&connector, &state);
+
+    assert!(wait(|| connector.metadata_requests().len() == 1).await);
+
+    worker.shutdown();
+
+    assert!(worker.join_timeout(Duration::from_secs(TIMEOUT)).await);
+    assert_eq!(commit_of(&state, "first"), None);
+}